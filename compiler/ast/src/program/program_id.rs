@@ -16,6 +16,8 @@
 
 use crate::Identifier;
 
+use leo_span::Symbol;
+
 use core::fmt;
 use serde::{de, de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
@@ -75,8 +77,10 @@ impl<'de> Deserialize<'de> for ProgramId {
                 // Convert the serialized string into a BTreeMap to recover ProgramId.
                 let key: BTreeMap<String, String> = to_json_string(value)?;
 
+                // The name is serialized as a plain string, which is recovered as an identifier without a span.
                 let name: Identifier = match key.get("name") {
-                    Some(name) => to_json_string(name)?,
+                    Some(name) => to_json_string(name)
+                        .unwrap_or_else(|_: E| Identifier::new(Symbol::intern(name), Default::default())),
                     None => return Err(E::custom("missing 'name' in serialized ProgramId struct")),
                 };
 
@@ -25,11 +25,17 @@ use leo_passes::*;
 use leo_span::{source_map::FileName, symbol::with_session_globals};
 
 use sha2::{Digest, Sha256};
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 use crate::CompilerOptions;
 
 /// The primary entry point of the Leo compiler.
+///
+/// # Thread safety
+///
+/// A `Compiler` is not `Send`, as it borrows a [`Handler`], which is neither `Send` nor `Sync`.
+/// Every `Symbol` it produces is interned in the session globals, which are set for the thread
+/// running the session, so its ASTs and symbol tables are only meaningful on that thread.
 #[derive(Clone)]
 pub struct Compiler<'a> {
     /// The handler is used for error and warning emissions.
@@ -54,6 +60,8 @@ pub struct Compiler<'a> {
     assigner: Assigner,
     /// The type table.
     type_table: TypeTable,
+    /// The paths of imports, by import name, which are read instead of `imports/{name}.leo`.
+    import_paths: BTreeMap<String, PathBuf>,
 }
 
 impl<'a> Compiler<'a> {
@@ -81,9 +89,16 @@ impl<'a> Compiler<'a> {
             node_builder,
             assigner,
             type_table,
+            import_paths: BTreeMap::new(),
         }
     }
 
+    /// Returns the compiler reading the imports with the given names from the given paths, instead of `imports/`.
+    pub fn with_import_paths(mut self, import_paths: BTreeMap<String, PathBuf>) -> Self {
+        self.import_paths = import_paths;
+        self
+    }
+
     /// Returns a SHA256 checksum of the program file.
     pub fn checksum(&self) -> Result<String> {
         // Read in the main file as string
@@ -104,7 +119,13 @@ impl<'a> Compiler<'a> {
         let prg_sf = with_session_globals(|s| s.source_map.new_source(program_string, name));
 
         // Use the parser to construct the abstract syntax tree (ast).
-        self.ast = leo_parser::parse_ast(self.handler, &self.node_builder, &prg_sf.src, prg_sf.start_pos)?;
+        self.ast = leo_parser::parse_ast_with_imports(
+            self.handler,
+            &self.node_builder,
+            &prg_sf.src,
+            prg_sf.start_pos,
+            &self.import_paths,
+        )?;
 
         // If the program is imported, then check that the name of its program scope matches the file name.
         // Note that parsing enforces that there is exactly one program scope in a file.
@@ -176,6 +197,11 @@ impl<'a> Compiler<'a> {
         Ok(symbol_table)
     }
 
+    /// Runs the unused import checking pass.
+    pub fn unused_import_pass(&self) {
+        UnusedImportChecker::do_pass((&self.ast, self.handler))
+    }
+
     /// Runs the type checker pass.
     pub fn type_checker_pass(&'a self, symbol_table: SymbolTable) -> Result<(SymbolTable, StructGraph, CallGraph)> {
         let (symbol_table, struct_graph, call_graph) =
@@ -301,6 +327,8 @@ impl<'a> Compiler<'a> {
 
     /// Runs the compiler stages.
     pub fn compiler_stages(&mut self) -> Result<(SymbolTable, StructGraph, CallGraph)> {
+        self.unused_import_pass();
+
         let st = self.symbol_table_pass()?;
         let (st, struct_graph, call_graph) = self.type_checker_pass(st)?;
 
@@ -0,0 +1,360 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The formatter to re-emit Leo code text with canonical whitespace.
+//!
+//! This module contains the [`format_source()`] method, which tokenizes the code and prints the tokens again,
+//! re-indenting each line and normalizing the spacing between tokens.
+//! Comments and line breaks are preserved, and consecutive blank lines are collapsed into one.
+
+use crate::{parser, tokenize, SpannedToken, Token};
+
+use leo_ast::NodeBuilder;
+use leo_errors::{emitter::Handler, ParserError, Result};
+use leo_span::span::{BytePos, Pos};
+
+/// The indentation of one level of nesting.
+const INDENT: &str = "    ";
+
+/// A token along with its text in the source and the number of line breaks before it.
+struct FormatToken {
+    /// The token.
+    token: Token,
+    /// The text of the token, which for line comments excludes the line break.
+    text: String,
+    /// The number of line breaks between this token and the previous one.
+    line_breaks: usize,
+    /// Whether there is whitespace between this token and the previous one.
+    spaced: bool,
+}
+
+/// Formats the given Leo source code text of a program.
+///
+/// The source is parsed first, so that only well-formed programs are formatted.
+/// Trailing commas are added to struct and record declarations spanning multiple lines.
+pub fn format_source(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+) -> Result<String> {
+    parser::parse(handler, node_builder, source, start_pos)?;
+
+    let tokens = tokenize(source, start_pos)?;
+    let mut tokens = format_tokens(source, start_pos, &tokens);
+    add_trailing_commas(&mut tokens);
+    let formatted = print(&tokens);
+
+    // Formatting may only change whitespace and add trailing commas, so the tokens must be the same as before.
+    let reformatted = tokenize(&formatted, BytePos(0))?;
+    if !tokens.iter().map(|t| &t.text).eq(format_tokens(&formatted, BytePos(0), &reformatted).iter().map(|t| &t.text)) {
+        return Err(ParserError::formatting_changed_tokens().into());
+    }
+
+    Ok(formatted)
+}
+
+/// Pairs the tokens with their text in `source` and the line breaks preceding them.
+fn format_tokens(source: &str, start_pos: BytePos, tokens: &[SpannedToken]) -> Vec<FormatToken> {
+    let offset = |pos: BytePos| pos.to_usize() - start_pos.to_usize();
+
+    let mut end = 0;
+    let mut ends_with_line_break = false;
+    tokens
+        .iter()
+        .map(|SpannedToken { token, span }| {
+            let (lo, hi) = (offset(span.lo), offset(span.hi));
+            let text = &source[lo..hi];
+
+            // Line comments include the line break ending them.
+            let line_breaks = source[end..lo].matches('\n').count() + ends_with_line_break as usize;
+            let spaced = end < lo || ends_with_line_break;
+            ends_with_line_break = text.ends_with('\n');
+            end = hi;
+
+            let text = match token {
+                Token::CommentLine(_) | Token::CommentBlock(_) => text.trim_end().to_string(),
+                _ => text.to_string(),
+            };
+            FormatToken { token: token.clone(), text, line_breaks, spaced }
+        })
+        .collect()
+}
+
+/// Adds trailing commas to the struct and record declarations spanning multiple lines.
+fn add_trailing_commas(tokens: &mut Vec<FormatToken>) {
+    // Whether each of the open delimiters opens the body of a declaration.
+    let mut delimiters: Vec<bool> = Vec::new();
+    let mut last_significant: Option<usize> = None;
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i].token {
+            Token::LeftCurly | Token::LeftParen | Token::LeftSquare => {
+                let is_declaration = matches!(last_significant, Some(j) if j >= 1
+                    && matches!(tokens[j].token, Token::Identifier(_))
+                    && matches!(tokens[j - 1].token, Token::Struct | Token::Record));
+                delimiters.push(is_declaration && tokens[i].token == Token::LeftCurly);
+            }
+            Token::RightCurly | Token::RightParen | Token::RightSquare => {
+                let is_declaration = delimiters.pop().unwrap_or_default();
+                if let Some(j) = last_significant {
+                    let spans_lines = tokens[j + 1..=i].iter().any(|t| t.line_breaks > 0);
+                    let ends_member = !matches!(tokens[j].token, Token::Comma | Token::Semicolon | Token::LeftCurly);
+                    if is_declaration && spans_lines && ends_member {
+                        tokens.insert(j + 1, FormatToken {
+                            token: Token::Comma,
+                            text: ",".to_string(),
+                            line_breaks: 0,
+                            spaced: false,
+                        });
+                        i += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+        if !matches!(tokens[i].token, Token::CommentLine(_) | Token::CommentBlock(_)) {
+            last_significant = Some(i);
+        }
+        i += 1;
+    }
+}
+
+/// Prints the tokens, indenting each line by its nesting depth.
+fn print(tokens: &[FormatToken]) -> String {
+    let mut output = String::new();
+    let mut depth: usize = 0;
+    // The depths of the `?` of the ternary expressions whose `:` has not been printed yet.
+    let mut open_ternaries: Vec<usize> = Vec::new();
+    let mut previous: Option<&FormatToken> = None;
+    // The previous token that is not a comment.
+    let mut previous_significant: Option<&FormatToken> = None;
+    // Whether the previous token is glued to the next one, as negations and the `/` of locators are.
+    let mut previous_is_glued = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let starts_line = i == 0 || token.line_breaks > 0;
+        if starts_line && i > 0 {
+            // Blank lines are kept, but not at the start or end of a block.
+            let opens_block = previous.map_or(false, |p| is_open_delimiter(&p.token));
+            let blank_line = token.line_breaks > 1 && !opens_block && !is_close_delimiter(&token.token);
+            output.push('\n');
+            if blank_line {
+                output.push('\n');
+            }
+        }
+
+        if starts_line {
+            // Lines starting with closing delimiters are indented at the level of the line opening them.
+            let closing = tokens[i..]
+                .iter()
+                .take_while(|t| is_close_delimiter(&t.token))
+                .enumerate()
+                .take_while(|(k, t)| *k == 0 || t.line_breaks == 0)
+                .count();
+            // Lines continuing an expression are indented one more level.
+            let continues = closing == 0
+                && (previous_significant.map_or(false, |p| is_operator(&p.token) || p.token == Token::Return)
+                    || is_operator(&token.token)
+                    || token.token == Token::Dot);
+            for _ in 0..depth.saturating_sub(closing) + continues as usize {
+                output.push_str(INDENT);
+            }
+        } else if let Some(previous) = previous {
+            let is_ternary_colon = token.token == Token::Colon && open_ternaries.last() == Some(&depth);
+            if !previous_is_glued && space_between(previous, token, is_ternary_colon) {
+                output.push(' ');
+            }
+        }
+        output.push_str(&token.text);
+
+        match &token.token {
+            t if is_open_delimiter(t) => depth += 1,
+            t if is_close_delimiter(t) => depth = depth.saturating_sub(1),
+            Token::Question => open_ternaries.push(depth),
+            Token::Colon if open_ternaries.last() == Some(&depth) => {
+                open_ternaries.pop();
+            }
+            _ => {}
+        }
+
+        // A `-` is negation unless it follows an operand, and a `/` following `.leo` is part of a locator.
+        previous_is_glued = match token.token {
+            Token::Sub | Token::Not => previous.map_or(true, |p| !ends_operand(&p.token)),
+            Token::Div => previous.map_or(false, |p| p.token == Token::Leo),
+            _ => false,
+        };
+        previous = Some(token);
+        if !matches!(token.token, Token::CommentLine(_) | Token::CommentBlock(_)) {
+            previous_significant = Some(token);
+        }
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Returns `true` if a space is printed between the tokens `left` and `right` on the same line.
+fn space_between(left: &FormatToken, right: &FormatToken, is_ternary_colon: bool) -> bool {
+    use Token::*;
+
+    match (&left.token, &right.token) {
+        (CommentLine(_) | CommentBlock(_), _) | (_, CommentLine(_) | CommentBlock(_)) => true,
+        (LeftParen | LeftSquare, _) | (_, RightParen | RightSquare) => false,
+        (LeftCurly, RightCurly) => false,
+        (_, Comma | Semicolon) => false,
+        (Comma | Semicolon, _) => true,
+        (Dot | DotDot | DoubleColon | At, _) | (_, Dot | DotDot | DoubleColon) => false,
+        (Leo, Div) => false,
+        (_, Colon) => is_ternary_colon,
+        (Colon, _) => true,
+        // Calls and indexing.
+        (Identifier(_) | Assert | AssertEq | AssertNeq | Finalize, LeftParen) => false,
+        (Identifier(_) | RightParen | RightSquare, LeftSquare) => false,
+        // The type suffixes of literals, as in `1u8` and `(0, 1)group`.
+        (Integer(_) | RightParen, r) if is_type_keyword(r) => right.spaced,
+        _ => true,
+    }
+}
+
+/// Returns `true` if the token is a type keyword that may suffix a literal.
+fn is_type_keyword(token: &Token) -> bool {
+    use Token::*;
+
+    matches!(token, Field | Group | Scalar | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128)
+}
+
+/// Returns `true` if the token may end an operand of a binary operator.
+fn ends_operand(token: &Token) -> bool {
+    use Token::*;
+
+    is_type_keyword(token)
+        || matches!(
+            token,
+            Identifier(_)
+                | Integer(_)
+                | StaticString(_)
+                | AddressLit(_)
+                | True
+                | False
+                | SelfLower
+                | Block
+                | RightParen
+                | RightSquare
+                | RightCurly
+        )
+}
+
+/// Returns `true` if the token is a binary operator, an assignment, or the `?` of a ternary expression.
+fn is_operator(token: &Token) -> bool {
+    use Token::*;
+
+    matches!(
+        token,
+        And | AndAssign
+            | Or
+            | OrAssign
+            | BitAnd
+            | BitAndAssign
+            | BitOr
+            | BitOrAssign
+            | BitXor
+            | BitXorAssign
+            | Eq
+            | NotEq
+            | Lt
+            | LtEq
+            | Gt
+            | GtEq
+            | Add
+            | AddAssign
+            | Sub
+            | SubAssign
+            | Mul
+            | MulAssign
+            | Div
+            | DivAssign
+            | Pow
+            | PowAssign
+            | Rem
+            | RemAssign
+            | Shl
+            | ShlAssign
+            | Shr
+            | ShrAssign
+            | Assign
+            | Question
+    )
+}
+
+/// Returns `true` if the token opens a delimited sequence.
+fn is_open_delimiter(token: &Token) -> bool {
+    matches!(token, Token::LeftCurly | Token::LeftParen | Token::LeftSquare)
+}
+
+/// Returns `true` if the token closes a delimited sequence.
+fn is_close_delimiter(token: &Token) -> bool {
+    matches!(token, Token::RightCurly | Token::RightParen | Token::RightSquare)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then};
+
+    #[test]
+    fn test_format_source() {
+        create_session_if_not_set_then(|s| {
+            let raw = r#"
+program test.aleo {
+  struct Point {x: u8,
+      y: u8
+  }
+
+
+  // Adds the coordinates.
+  transition add(p:Point,q:Point)->Point{
+      let x: u8=p.x+q.x; // The sum of x.
+      let y: u8 = -1i8 as u8 + (p.y*q.y)   ;
+      return Point {x,y};
+  }
+}
+"#;
+            let expected = r#"program test.aleo {
+    struct Point { x: u8,
+        y: u8,
+    }
+
+    // Adds the coordinates.
+    transition add(p: Point, q: Point) -> Point {
+        let x: u8 = p.x + q.x; // The sum of x.
+        let y: u8 = -1i8 as u8 + (p.y * q.y);
+        return Point { x, y };
+    }
+}
+"#;
+
+            let handler = Handler::default();
+            let sf = s.source_map.new_source(raw, FileName::Custom("test".into()));
+            let formatted = format_source(&handler, &NodeBuilder::default(), &sf.src, sf.start_pos).unwrap();
+            assert_eq!(formatted, expected);
+
+            // Formatting is idempotent.
+            let sf = s.source_map.new_source(&formatted, FileName::Custom("formatted".into()));
+            assert_eq!(format_source(&handler, &NodeBuilder::default(), &sf.src, sf.start_pos).unwrap(), formatted);
+        })
+    }
+}
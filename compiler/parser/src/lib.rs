@@ -31,9 +31,14 @@ pub(crate) use tokenizer::*;
 pub mod parser;
 pub use parser::*;
 
+pub mod formatter;
+pub use formatter::*;
+
 use leo_ast::{input::InputData, Ast, NodeBuilder, ProgramInput};
 use leo_errors::{emitter::Handler, Result};
 
+use std::{collections::BTreeMap, path::PathBuf};
+
 #[cfg(test)]
 mod test;
 
@@ -42,6 +47,18 @@ pub fn parse_ast(handler: &Handler, node_builder: &NodeBuilder, source: &str, st
     Ok(Ast::new(parser::parse(handler, node_builder, source, start_pos)?))
 }
 
+/// Creates a new AST from a given file path and source code text, reading the imports with the given names
+/// from the given paths instead of the `imports/` directory.
+pub fn parse_ast_with_imports(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+    import_paths: &BTreeMap<String, PathBuf>,
+) -> Result<Ast> {
+    Ok(Ast::new(parser::parse_with_imports(handler, node_builder, source, start_pos, import_paths)?))
+}
+
 /// Parses program inputs from the input file path
 pub fn parse_program_inputs(
     handler: &Handler,
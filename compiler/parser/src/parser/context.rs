@@ -20,7 +20,7 @@ use leo_ast::*;
 use leo_errors::{emitter::Handler, ParserError, ParserWarning, Result};
 use leo_span::{Span, Symbol};
 
-use std::{fmt::Display, mem};
+use std::{collections::BTreeMap, fmt::Display, mem, path::PathBuf};
 
 /// Stores a program in tokenized format plus additional context.
 /// May be converted into a [`Program`] AST by parsing all tokens.
@@ -41,6 +41,8 @@ pub(crate) struct ParserContext<'a> {
     pub(crate) disallow_struct_construction: bool,
     /// true if parsing an identifier inside an input file.
     pub(crate) allow_identifier_underscores: bool,
+    /// The paths of imports, by import name, which are read instead of `imports/{name}.leo`.
+    pub(crate) import_paths: Option<&'a BTreeMap<String, PathBuf>>,
 }
 
 /// Dummy span used to appease borrow checker.
@@ -60,6 +62,7 @@ impl<'a> ParserContext<'a> {
             node_builder,
             disallow_struct_construction: false,
             allow_identifier_underscores: false,
+            import_paths: None,
             prev_token: token.clone(),
             token,
             tokens,
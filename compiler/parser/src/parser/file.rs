@@ -15,7 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::parse_ast;
+use crate::{parse_ast, parse_ast_with_imports};
 use leo_errors::{CompilerError, ParserError, Result};
 use leo_span::{source_map::FileName, symbol::with_session_globals};
 
@@ -87,10 +87,18 @@ impl ParserContext<'_> {
 
         // Tokenize and parse import file.
         // Todo: move this to a different module.
-        let mut import_file_path =
-            std::env::current_dir().map_err(|err| CompilerError::cannot_open_cwd(err, self.token.span))?;
-        import_file_path.push("imports");
-        import_file_path.push(format!("{}.leo", import_name.name));
+        // Imports mapped to a path are read from it, and other imports from the `imports/` directory.
+        let mapped_path = self.import_paths.and_then(|paths| paths.get(&import_name.name.to_string()));
+        let import_file_path = match mapped_path {
+            Some(path) => path.clone(),
+            None => {
+                let mut import_file_path =
+                    std::env::current_dir().map_err(|err| CompilerError::cannot_open_cwd(err, self.token.span))?;
+                import_file_path.push("imports");
+                import_file_path.push(format!("{}.leo", import_name.name));
+                import_file_path
+            }
+        };
 
         // Throw an error if the import file doesn't exist.
         if !import_file_path.exists() {
@@ -109,7 +117,13 @@ impl ParserContext<'_> {
         let prg_sf = with_session_globals(|s| s.source_map.new_source(&program_string, name));
 
         // Use the parser to construct the imported abstract syntax tree (ast).
-        let program_ast = parse_ast(self.handler, self.node_builder, &prg_sf.src, prg_sf.start_pos)?;
+        // The imports of the imported program are resolved with the same paths.
+        let program_ast = match self.import_paths {
+            Some(import_paths) => {
+                parse_ast_with_imports(self.handler, self.node_builder, &prg_sf.src, prg_sf.start_pos, import_paths)?
+            }
+            None => parse_ast(self.handler, self.node_builder, &prg_sf.src, prg_sf.start_pos)?,
+        };
 
         Ok((import_name.name, (program_ast.into_repr(), start + end)))
     }
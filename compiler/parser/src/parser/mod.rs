@@ -27,7 +27,7 @@ use leo_span::Span;
 
 use indexmap::IndexMap;
 use leo_span::span::BytePos;
-use std::unreachable;
+use std::{collections::BTreeMap, path::PathBuf, unreachable};
 
 mod context;
 pub(super) use context::ParserContext;
@@ -45,6 +45,21 @@ pub fn parse(handler: &Handler, node_builder: &NodeBuilder, source: &str, start_
     tokens.parse_program()
 }
 
+/// Creates a new program from a given file path and source code text, reading the imports with the given names
+/// from the given paths instead of the `imports/` directory.
+pub fn parse_with_imports(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+    import_paths: &BTreeMap<String, PathBuf>,
+) -> Result<Program> {
+    let mut tokens = ParserContext::new(handler, node_builder, crate::tokenize(source, start_pos)?);
+    tokens.import_paths = Some(import_paths);
+
+    tokens.parse_program()
+}
+
 /// Parses an input file at the given file `path` and `source` code text.
 pub fn parse_input(
     handler: &Handler,
@@ -49,3 +49,6 @@ pub use symbol_table_creation::*;
 
 pub mod type_checking;
 pub use type_checking::*;
+
+pub mod unused_import_checking;
+pub use unused_import_checking::*;
@@ -205,8 +205,11 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
 
     fn visit_function(&mut self, function: &'a Function) {
         // Check that the function's annotations are valid.
-        // Note that Leo does not natively support any specific annotations.
+        // The only annotation is `@test`, which marks the transitions run by `leo test`.
         for annotation in function.annotations.iter() {
+            if annotation.identifier.name == sym::test && matches!(function.variant, Variant::Transition) {
+                continue;
+            }
             // TODO: Change to compiler warning.
             self.emit_err(TypeCheckerError::unknown_annotation(annotation, annotation.span))
         }
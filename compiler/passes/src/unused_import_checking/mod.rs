@@ -0,0 +1,34 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod unused_import_checker;
+pub use unused_import_checker::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::emitter::Handler;
+
+impl<'a> Pass for UnusedImportChecker<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = ();
+
+    /// Runs the compiler pass.
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut visitor = UnusedImportChecker::new(handler);
+        visitor.visit_program(ast.as_repr());
+    }
+}
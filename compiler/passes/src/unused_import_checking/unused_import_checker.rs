@@ -0,0 +1,140 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_errors::{emitter::Handler, CompilerWarning};
+use leo_span::Symbol;
+
+use indexmap::IndexSet;
+
+/// A compiler pass during which a warning is emitted for each import the program never refers to.
+/// Note that this pass only inspects the main program, not the imported programs.
+pub struct UnusedImportChecker<'a> {
+    /// The names of the external programs referred to by calls, inputs, and outputs.
+    programs: IndexSet<Symbol>,
+    /// The names of the struct and record types referred to by the program.
+    types: IndexSet<Symbol>,
+    /// The error handler.
+    handler: &'a Handler,
+}
+
+impl<'a> UnusedImportChecker<'a> {
+    pub fn new(handler: &'a Handler) -> Self {
+        Self { programs: Default::default(), types: Default::default(), handler }
+    }
+
+    /// Records the struct and record types referred to by the given type.
+    fn visit_type(&mut self, type_: &Type) {
+        match type_ {
+            Type::Array(array) => self.visit_type(array.element_type()),
+            Type::Identifier(identifier) => {
+                self.types.insert(identifier.name);
+            }
+            Type::Mapping(mapping) => {
+                self.visit_type(&mapping.key);
+                self.visit_type(&mapping.value);
+            }
+            Type::Tuple(tuple) => tuple.elements().iter().for_each(|type_| self.visit_type(type_)),
+            _ => {}
+        }
+    }
+
+    /// Records the types and external programs referred to by the given function inputs and outputs.
+    fn visit_signature(&mut self, inputs: &[Input], outputs: &[Output]) {
+        for input in inputs {
+            if let Input::External(external) = input {
+                self.programs.insert(external.program_name.name);
+            }
+            self.visit_type(&input.type_());
+        }
+        for output in outputs {
+            if let Output::External(external) = output {
+                self.programs.insert(external.program_name.name);
+            }
+            self.visit_type(&output.type_());
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for UnusedImportChecker<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        if let Some(Expression::Identifier(program)) = input.external.as_deref() {
+            self.programs.insert(program.name);
+        }
+        input.arguments.iter().for_each(|expr| {
+            self.visit_expression(expr, additional);
+        });
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.types.insert(input.name.name);
+        input.members.iter().filter_map(|member| member.expression.as_ref()).for_each(|expr| {
+            self.visit_expression(expr, additional);
+        });
+    }
+}
+
+impl<'a> StatementVisitor<'a> for UnusedImportChecker<'a> {
+    fn visit_const(&mut self, input: &'a ConstDeclaration) {
+        self.visit_type(&input.type_);
+        self.visit_expression(&input.value, &Default::default());
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.visit_type(&input.type_);
+        self.visit_expression(&input.value, &Default::default());
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for UnusedImportChecker<'a> {
+    fn visit_program(&mut self, input: &'a Program) {
+        input.program_scopes.values().for_each(|scope| self.visit_program_scope(scope));
+
+        // An import is used if the program refers to the imported program, or to a struct or record it declares.
+        for (name, (import, span)) in input.imports.iter() {
+            let is_used = self.programs.contains(name)
+                || import
+                    .program_scopes
+                    .values()
+                    .flat_map(|scope| scope.structs.iter())
+                    .any(|(struct_name, _)| self.types.contains(struct_name));
+            if !is_used {
+                self.handler.emit_warning(CompilerWarning::unused_import(name, *span).into());
+            }
+        }
+    }
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        input.members.iter().for_each(|member| self.visit_type(&member.type_));
+    }
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.visit_type(&input.key_type);
+        self.visit_type(&input.value_type);
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.visit_signature(&input.input, &input.output);
+        self.visit_block(&input.block);
+        if let Some(finalize) = &input.finalize {
+            self.visit_signature(&finalize.input, &finalize.output);
+            self.visit_block(&finalize.block);
+        }
+    }
+}
@@ -270,6 +270,7 @@ symbols! {
     program,
     block,
     height,
+    test,
 }
 
 /// An interned string.
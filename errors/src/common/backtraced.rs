@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{diagnostic_json, render_uncolored};
+
 use std::fmt;
 
 use backtrace::Backtrace;
@@ -101,6 +103,11 @@ impl Backtraced {
             exit_code = self.code,
         )
     }
+
+    /// Renders the message as a line of JSON.
+    pub fn to_json(&self) -> String {
+        diagnostic_json(self, None, render_uncolored(self))
+    }
 }
 
 impl fmt::Display for Backtraced {
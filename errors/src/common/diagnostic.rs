@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Backtraced;
+
+use leo_span::{symbol::with_session_globals, Span};
+
+use serde_json::{json, Value};
+
+/// Renders a message as a line of JSON in the format of the rustc JSON diagnostics.
+/// `span` is the location the message points to, if it is a formatted message.
+pub(crate) fn diagnostic_json(backtraced: &Backtraced, span: Option<Span>, rendered: String) -> String {
+    let (level, code) = match backtraced.error {
+        true => ("error", backtraced.error_code()),
+        false => ("warning", backtraced.warning_code()),
+    };
+
+    let spans: Vec<Value> = span
+        .and_then(|span| {
+            with_session_globals(|s| {
+                let loc = s.source_map.span_to_location(span)?;
+                let lines = s.source_map.line_contents_of_span(span).unwrap_or_default();
+                let text: Vec<Value> = lines
+                    .lines()
+                    .enumerate()
+                    .map(|(i, line)| {
+                        // Only the first and last lines of a multiline span are partially highlighted.
+                        let highlight_start = if i == 0 { loc.col_start } else { 1 };
+                        let highlight_end =
+                            if loc.line_start + i == loc.line_stop { loc.col_stop } else { line.len() + 1 };
+                        json!({ "text": line, "highlight_start": highlight_start, "highlight_end": highlight_end })
+                    })
+                    .collect();
+                Some(json!({
+                    "file_name": loc.source_file.name.to_string(),
+                    "line_start": loc.line_start,
+                    "line_end": loc.line_stop,
+                    "column_start": loc.col_start,
+                    "column_end": loc.col_stop,
+                    "is_primary": true,
+                    "text": text,
+                }))
+            })
+        })
+        .into_iter()
+        .collect();
+
+    let children: Vec<Value> = backtraced
+        .help
+        .iter()
+        .map(|help| json!({ "message": help, "code": null, "level": "help", "spans": [], "children": [] }))
+        .collect();
+
+    json!({
+        "$message_type": "diagnostic",
+        "message": backtraced.message,
+        "code": { "code": code, "explanation": null },
+        "level": level,
+        "spans": spans,
+        "children": children,
+        "rendered": rendered,
+    })
+    .to_string()
+}
+
+/// Renders a message that has no Leo error code, such as a snarkVM error, as a line of JSON.
+pub(crate) fn uncoded_diagnostic_json(message: String) -> String {
+    json!({
+        "$message_type": "diagnostic",
+        "message": message,
+        "code": null,
+        "level": "error",
+        "spans": [],
+        "children": [],
+        "rendered": message,
+    })
+    .to_string()
+}
+
+/// Displays a message without the terminal colors, which are meaningless in JSON.
+pub(crate) fn render_uncolored(message: &impl std::fmt::Display) -> String {
+    colored::control::set_override(false);
+    let rendered = message.to_string();
+    colored::control::unset_override();
+    rendered
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Backtraced, INDENT};
+use crate::{diagnostic_json, render_uncolored, Backtraced, INDENT};
 
 use leo_span::{source_map::SpanLocation, symbol::with_session_globals, Span};
 
@@ -84,6 +84,11 @@ impl Formatted {
     pub fn warning_code(&self) -> String {
         self.backtrace.warning_code()
     }
+
+    /// Renders the message as a line of JSON, including the source lines it points to.
+    pub fn to_json(&self) -> String {
+        diagnostic_json(&self.backtrace, Some(self.span), render_uncolored(self))
+    }
 }
 
 impl fmt::Display for Formatted {
@@ -142,7 +147,8 @@ impl fmt::Display for Formatted {
             start = loc.col_start,
         )?;
 
-        for (line_no, line) in contents.lines().enumerate() {
+        let lines = contents.lines().collect::<Vec<_>>();
+        for (line_no, line) in lines.iter().enumerate() {
             writeln!(
                 f,
                 "{line_no:width$} | {text}",
@@ -150,8 +156,19 @@ impl fmt::Display for Formatted {
                 line_no = loc.line_start + line_no,
                 text = line,
             )?;
+            // The start of a span over several lines is underlined up to the end of its first line.
+            if line_no == 0 && lines.len() > 1 {
+                writeln!(f, "{INDENT     } |{}", underline(loc.col_start, line.chars().count() + 1))?;
+            }
         }
 
+        // The end of a span over several lines is underlined from the start of the text of its last line.
+        let underlined = match lines.last() {
+            Some(last) if lines.len() > 1 => {
+                underline(last.chars().take_while(|c| c.is_whitespace()).count() + 1, loc.col_stop)
+            }
+            _ => underlined,
+        };
         write!(f, "{INDENT     } |{underlined}",)?;
 
         if let Some(help) = &self.backtrace.help {
@@ -86,6 +86,16 @@ macro_rules! create_messages {
         }
 
 
+        impl $type_ {
+            /// Renders the message as a line of JSON.
+            pub fn to_json(&self) -> String {
+                match self {
+                    Self::Formatted(formatted) => formatted.to_json(),
+                    Self::Backtraced(backtraced) => backtraced.to_json()
+                }
+            }
+        }
+
         // Steps over the list of functions with an initial code of 0.
         impl $type_ {
             create_messages!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps),)*);
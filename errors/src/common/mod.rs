@@ -18,6 +18,10 @@
 pub mod backtraced;
 pub use self::backtraced::*;
 
+/// This module contains the rendering of messages as JSON diagnostics.
+mod diagnostic;
+pub(crate) use self::diagnostic::*;
+
 /// This module contains a formatted error and its methods.
 pub mod formatted;
 pub use self::formatted::*;
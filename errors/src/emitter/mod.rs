@@ -18,6 +18,7 @@ use crate::LeoWarning;
 
 use super::LeoError;
 use core::{default::Default, fmt};
+use serde_json::{json, Value};
 use std::{cell::RefCell, rc::Rc};
 
 /// Types that are sinks for compiler errors.
@@ -53,6 +54,128 @@ impl Emitter for StderrEmitter {
     }
 }
 
+/// An `Emitter` writing each message as a line of JSON to the standard error, for editor integration.
+/// The lines follow the format of the rustc JSON diagnostics.
+#[derive(Default)]
+pub struct JsonEmitter {
+    /// Exit code of the last emitted error.
+    last_error_code: Option<i32>,
+}
+
+impl JsonEmitter {
+    /// Returns a new JSON emitter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit_err(&mut self, err: LeoError) {
+        self.last_error_code = Some(err.exit_code());
+        eprintln!("{}", err.to_json());
+    }
+
+    fn last_emitted_err_code(&self) -> Option<i32> {
+        self.last_error_code
+    }
+
+    fn emit_warning(&mut self, warning: LeoWarning) {
+        eprintln!("{}", warning.to_json());
+    }
+}
+
+/// An `Emitter` printing messages to the standard error, which also collects the messages pointing to source lines
+/// as the results of a SARIF 2.1.0 log, for code scanning.
+#[derive(Default, Clone)]
+pub struct SarifEmitter {
+    /// Exit code of the last emitted error.
+    last_error_code: Option<i32>,
+    /// The SARIF results of the messages, which are shared with the clones of the emitter.
+    results: Rc<RefCell<Vec<Value>>>,
+}
+
+impl SarifEmitter {
+    /// Returns a new SARIF emitter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the message with the given JSON diagnostic, if it points to a source line.
+    fn collect(&self, diagnostic: &str) {
+        let Ok(diagnostic) = serde_json::from_str::<Value>(diagnostic) else {
+            return;
+        };
+        let Some(span) = diagnostic["spans"].get(0) else {
+            return;
+        };
+        self.results.borrow_mut().push(json!({
+            "ruleId": diagnostic["code"]["code"],
+            "level": diagnostic["level"],
+            "message": { "text": diagnostic["message"] },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": sarif_uri(span["file_name"].as_str().unwrap_or_default()) },
+                    "region": {
+                        "startLine": span["line_start"],
+                        "startColumn": span["column_start"],
+                        "endLine": span["line_end"],
+                        "endColumn": span["column_end"],
+                    },
+                },
+            }],
+        }));
+    }
+
+    /// Returns the SARIF log of the collected messages.
+    pub fn to_sarif(&self) -> String {
+        let results = self.results.borrow();
+        let mut rule_ids = results.iter().filter_map(|result| result["ruleId"].as_str()).collect::<Vec<_>>();
+        rule_ids.sort_unstable();
+        rule_ids.dedup();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "leo",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://github.com/AleoHQ/leo",
+                        "rules": rule_ids.into_iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                    },
+                },
+                "results": *results,
+            }],
+        });
+        serde_json::to_string_pretty(&log).unwrap_or_default()
+    }
+}
+
+/// Returns the URI of the file with the given name, relative to the current directory if it is in it.
+fn sarif_uri(file_name: &str) -> String {
+    let path = std::path::Path::new(file_name);
+    let relative = std::env::current_dir().ok().and_then(|dir| path.strip_prefix(dir).ok().map(|path| path.to_owned()));
+    relative.unwrap_or_else(|| path.to_owned()).to_string_lossy().replace('\\', "/")
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_err(&mut self, err: LeoError) {
+        self.last_error_code = Some(err.exit_code());
+        self.collect(&err.to_json());
+        eprintln!("{err}");
+    }
+
+    fn last_emitted_err_code(&self) -> Option<i32> {
+        self.last_error_code
+    }
+
+    fn emit_warning(&mut self, warning: LeoWarning) {
+        self.collect(&warning.to_json());
+        eprintln!("{warning}");
+    }
+}
+
 /// A buffer of `T`s.
 #[derive(Debug)]
 pub struct Buffer<T>(Vec<T>);
@@ -211,9 +334,9 @@ impl Handler {
     /// Emits the error `err`.
     /// This will immediately abort compilation.
     pub fn fatal_err(&self, err: LeoError) -> ! {
-        let code = err.exit_code();
+        let status = err.exit_status();
         self.emit_err(err);
-        std::process::exit(code);
+        std::process::exit(status);
     }
 
     /// The number of errors thus far.
@@ -296,4 +419,59 @@ mod tests {
             Handler::with(|_| Ok(())).unwrap();
         })
     }
+
+    #[test]
+    fn json_diagnostics() {
+        create_session_if_not_set_then(|_| {
+            let err: LeoError = ParserError::unexpected_eof(Span::default()).into();
+            let json: serde_json::Value = serde_json::from_str(&err.to_json()).unwrap();
+            assert_eq!(json["level"], "error");
+            assert_eq!(json["code"]["code"], err.error_code());
+            assert_eq!(json["message"], "unexpected EOF");
+            assert!(!json["rendered"].as_str().unwrap().contains('\u{1b}'));
+        })
+    }
+
+    #[test]
+    fn sarif_results() {
+        create_session_if_not_set_then(|s| {
+            let source =
+                s.source_map.new_source("program foo.aleo {", leo_span::source_map::FileName::Custom("foo.leo".into()));
+            let span = Span::new(source.start_pos, source.start_pos + leo_span::span::BytePos(7));
+
+            let mut emitter = SarifEmitter::new();
+            emitter.emit_err(ParserError::unexpected_eof(span).into());
+            // Messages which don't point to a source line are not results.
+            emitter.emit_err(crate::CliError::needs_leo_build().into());
+
+            let sarif: serde_json::Value = serde_json::from_str(&emitter.to_sarif()).unwrap();
+            assert_eq!(sarif["version"], "2.1.0");
+            let results = sarif["runs"][0]["results"].as_array().unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0]["level"], "error");
+            assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 1);
+            assert_eq!(sarif["runs"][0]["tool"]["driver"]["rules"][0]["id"], results[0]["ruleId"]);
+        })
+    }
+
+    #[test]
+    fn exit_status_identifies_error_type() {
+        create_session_if_not_set_then(|_| {
+            let err: LeoError = ParserError::unexpected_eof(Span::default()).into();
+            assert_eq!(err.exit_status(), 10);
+            assert_eq!(LeoError::LastErrorCode(err.exit_code()).exit_status(), 10);
+
+            let err: LeoError = crate::CliError::needs_leo_build().into();
+            assert_eq!(err.exit_status(), 17);
+            let err: LeoError = crate::CliError::invalid_manifest_path("program.json", "missing").into();
+            assert_eq!(err.exit_status(), 20);
+            let err: LeoError = crate::CliError::warnings_denied(1).into();
+            assert_eq!(err.exit_status(), 21);
+            let err: LeoError = crate::CliError::avm_file_round_trip_mismatch("main.avm").into();
+            assert_eq!(err.exit_status(), 22);
+            let err: LeoError = crate::CliError::build_timed_out(1, "compiling", 0).into();
+            assert_eq!(LeoError::LastErrorCode(err.exit_code()).exit_status(), 23);
+            assert_eq!(LeoError::Anyhow(anyhow::anyhow!("snarkVM")).exit_status(), 1);
+        })
+    }
 }
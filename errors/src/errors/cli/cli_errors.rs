@@ -178,4 +178,422 @@ create_messages!(
         msg: format!("Failed to write file.\nIO Error: {error}"),
         help: None,
     }
+
+    @backtraced
+    avm_file_round_trip_mismatch {
+        args: (path: impl Display),
+        msg: format!("The AVM file `{path}` does not decode to the compiled program."),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_watch_files {
+        args: (error: impl Display),
+        msg: format!("Failed to watch the package files for changes.\nError: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    named_input_file_not_found {
+        args: (path: impl Display),
+        msg: format!("The input file `{path}` does not exist."),
+        help: Some("Create the input file, or omit `--input` to use the default input file.".to_string()),
+    }
+
+    @backtraced
+    warnings_denied {
+        args: (count: impl Display),
+        msg: format!("The build emitted {count} warning(s), which are denied by `--deny-warnings`."),
+        help: None,
+    }
+
+    @backtraced
+    directory_outside_of_package {
+        args: (path: impl Display),
+        msg: format!("Refusing to remove `{path}`, as it is outside of the package directory."),
+        help: None,
+    }
+
+    @backtraced
+    entry_file_not_in_source_directory {
+        args: (path: impl Display),
+        msg: format!("The entry file `{path}` is not a Leo file in the `src/` directory of the package."),
+        help: None,
+    }
+
+    @backtraced
+    unsupported_curve {
+        args: (curve: impl Display, supported: impl Display),
+        msg: format!("The curve `{curve}` is not supported by this build of Leo."),
+        help: Some(format!("The supported curves are: {supported}.")),
+    }
+
+    @backtraced
+    unformatted_files {
+        args: (count: impl Display),
+        msg: format!("{count} file(s) are not formatted."),
+        help: Some("Run `leo fmt` to format them.".to_string()),
+    }
+
+    @backtraced
+    failed_to_synthesize_keys {
+        args: (error: impl Display),
+        msg: format!("Failed to synthesize the proving and verifying keys.\nSnarkVM Error: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    keys_out_of_date {
+        args: (),
+        msg: "The proving and verifying keys do not match the compiled program.".to_string(),
+        help: Some("Run `leo setup` to synthesize the keys of the current program.".to_string()),
+    }
+
+    @backtraced
+    failed_to_prove {
+        args: (error: impl Display),
+        msg: format!("Failed to prove the execution.\nSnarkVM Error: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_verify {
+        args: (error: impl Display),
+        msg: format!("Failed to load the proof and verifying keys.\nSnarkVM Error: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    proof_verification_failed {
+        args: (error: impl Display),
+        msg: format!("The proof is invalid.\nSnarkVM Error: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    not_a_workspace {
+        args: (path: impl Display),
+        msg: format!("The manifest at `{path}` does not list any workspace members."),
+        help: Some("List the member packages in the `workspace.members` section of the manifest.".to_string()),
+    }
+
+    @backtraced
+    workspace_dependency_cycle {
+        args: (members: impl Display),
+        msg: format!("The workspace members {members} import each other in a cycle."),
+        help: None,
+    }
+
+    @backtraced
+    workspace_member_failed {
+        args: (member: impl Display),
+        msg: format!("Failed to build the workspace member `{member}`."),
+        help: None,
+    }
+
+    @formatted
+    extra_program_input {
+        args: (name: impl Display, function: impl Display, count: impl Display),
+        msg: format!("The input `{name}` is not an input of the function `{function}`, which has {count} input(s)."),
+        help: Some("Remove the input from the input file.".to_string()),
+    }
+
+    @formatted
+    mismatched_program_input {
+        args: (name: impl Display, register: impl Display, expected: impl Display, received: impl Display),
+        msg: format!("The input `{name}` is a `{received}`, but the function expects a `{expected}` in `{register}`."),
+        help: None,
+    }
+
+    @formatted
+    missing_program_input {
+        args: (function: impl Display, register: impl Display, expected: impl Display),
+        msg: format!("The input file is missing the input `{register}` of the function `{function}`."),
+        help: Some(format!("Add an input of type `{expected}` to the input file.")),
+    }
+
+    @backtraced
+    failed_to_configure_jobs {
+        args: (error: impl Display),
+        msg: format!("Failed to configure the number of jobs.\nError: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    invalid_stats_file {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to read the statistics in `{path}`.\nError: {error}"),
+        help: Some("Write the statistics of a build with `leo build --emit-stats`.".to_string()),
+    }
+
+    @backtraced
+    undefined_input_variable {
+        args: (variable: impl Display, path: impl Display),
+        msg: format!("The environment variable `{variable}` referenced in `{path}` is not set."),
+        help: Some(format!("Set `{variable}` in the environment of the build, or replace `${{{variable}}}` with a value.")),
+    }
+
+    @backtraced
+    invalid_import_path {
+        args: (name: impl Display, path: impl Display, reason: impl Display),
+        msg: format!("The import `{name}` can't be read from `{path}`, as {reason}."),
+        help: Some("Fix the path of the import in the `imports` section of `program.json`.".to_string()),
+    }
+
+    @backtraced
+    tests_failed {
+        args: (failed: impl Display),
+        msg: format!("{failed} test(s) failed."),
+        help: None,
+    }
+
+    @backtraced
+    checksum_mismatch {
+        args: (path: impl Display, stored: impl Display, current: impl Display),
+        msg: format!("The program checksum `{current}` doesn't match the checksum `{stored}` in `{path}`."),
+        help: Some("Rebuild the program with `leo build`, or check that the source is the one that was built.".to_string()),
+    }
+
+    @backtraced
+    build_timed_out {
+        args: (seconds: impl Display, status: impl Display, constraints: impl Display),
+        msg: format!("The build timed out after {seconds} seconds, while {status}, with {constraints} constraints synthesized so far."),
+        help: Some("The artifacts of the previous build are left intact. Raise the limit with `--timeout`.".to_string()),
+    }
+
+    @backtraced
+    missing_input_file {
+        args: (function: impl Display, parameters: impl Display),
+        msg: format!("`{function}` takes {parameters} input(s), but there is no input file to read them from."),
+        help: Some(format!("Pass the inputs after the name of the function, or write them to the `[{function}]` section of an input file in `inputs/`.")),
+    }
+
+    @backtraced
+    unknown_error_code {
+        args: (code: impl Display, codes: impl Display),
+        msg: format!("The error code `{code}` has no extended documentation."),
+        help: Some(format!("The explained error codes are: {codes}.")),
+    }
+
+    @backtraced
+    constraint_budget_exceeded {
+        args: (function: impl Display, constraints: impl Display, max_constraints: impl Display),
+        msg: format!("`{function}` has {constraints} constraints, more than the {max_constraints} allowed."),
+        help: Some("Reduce the size of the function, or raise the budget with `--max-constraints` or the `max_constraints` key in the `compiler` section of `program.json`.".to_string()),
+    }
+
+    @backtraced
+    input_directory_not_found {
+        args: (path: impl Display),
+        msg: format!("The input directory `{path}` does not exist."),
+        help: Some("Create the directory, or omit `--input-dir` to read the input file from `inputs/`.".to_string()),
+    }
+
+    @backtraced
+    input_file_not_in_directory {
+        args: (file: impl Display, directory: impl Display),
+        msg: format!("The input file `{file}` does not exist in the input directory `{directory}`."),
+        help: None,
+    }
+
+    @backtraced
+    ignored_import {
+        args: (name: impl Display, path: impl Display),
+        msg: format!("The import `{name}` is excluded from compilation, as `{path}` matches a pattern in `.leoignore`."),
+        help: Some("Remove the pattern from `.leoignore`, or remove the import.".to_string()),
+    }
+
+    @backtraced
+    failed_to_read_git_ref {
+        args: (git_ref: impl Display, error: impl Display),
+        msg: format!("Failed to read the package at the git revision `{git_ref}`.\nError: {error}"),
+        help: Some("The package must be in a git repository, and the revision must name a commit of it.".to_string()),
+    }
+
+    @backtraced
+    invalid_program_file {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to read the program `{path}`.\nError: {error}"),
+        help: Some("The file must hold Aleo instructions, or the bytes of a program written by `--emit-avm`.".to_string()),
+    }
+
+    @backtraced
+    unknown_bin {
+        args: (name: impl Display, bins: impl Display),
+        msg: format!("The `bins` section of `program.json` has no entry named `{name}`."),
+        help: Some(format!("The entries are: {bins}.")),
+    }
+
+    @backtraced
+    post_build_hook_failed {
+        args: (command: impl Display, error: impl Display),
+        msg: format!("The post-build hook `{command}` failed: {error}"),
+        help: Some("Pass `--hook-best-effort` to only warn when the hook fails.".to_string()),
+    }
+
+    @backtraced
+    unknown_program_format {
+        args: (path: impl Display),
+        msg: format!("The format of the program `{path}` can't be told from its extension."),
+        help: Some("Use the extension `.aleo` for Aleo instructions, or `.avm` for AVM bytecode.".to_string()),
+    }
+
+    @backtraced
+    input_include_cycle {
+        args: (cycle: impl Display),
+        msg: format!("The input files include each other: {cycle}."),
+        help: Some("Remove one of the `include` lines of the cycle.".to_string()),
+    }
+
+    @backtraced
+    input_include_not_found {
+        args: (path: impl Display, including: impl Display, error: impl Display),
+        msg: format!("Failed to read the input file `{path}` included by `{including}`.\nError: {error}"),
+        help: Some("Included files are resolved relative to the directory of the including file.".to_string()),
+    }
+
+    @backtraced
+    offline_forbidden {
+        args: (fetch: impl Display),
+        msg: format!("Fetching {fetch} requires network access, which `--offline` forbids."),
+        help: Some("Run the command without `--offline` or `LEO_OFFLINE`.".to_string()),
+    }
+
+    @backtraced
+    lib_file_not_found {
+        args: (path: impl Display),
+        msg: format!("`--lib-only` builds the library `{path}`, which doesn't exist."),
+        help: Some("Libraries are declared in `src/lib.leo`.".to_string()),
+    }
+
+    @backtraced
+    workspace_members_failed {
+        args: (members: impl Display),
+        msg: format!("Failed to build the workspace members {members}."),
+        help: Some("The errors of each member are printed after the log line announcing its build.".to_string()),
+    }
+
+    @backtraced
+    source_directory_not_found {
+        args: (path: impl Display),
+        msg: format!("The source directory `{path}` doesn't exist."),
+        help: Some("The source directory is set by `--source-dir`, or by `source_dir` in the `build` section of `program.json`.".to_string()),
+    }
+
+    @backtraced
+    unused_imports_found {
+        args: (count: impl Display),
+        msg: format!("Found unused imports in the programs of the package: {count}."),
+        help: Some("Remove the import statements of the programs which are not used.".to_string()),
+    }
+
+    @backtraced
+    audit_failed {
+        args: (checks: impl Display),
+        msg: format!("The package failed the checks: {checks}."),
+        help: None,
+    }
+
+    @backtraced
+    circuit_changed {
+        args: (file_name: impl Display),
+        msg: format!("`--frozen` forbids recompiling '{file_name}', which changed since the last build."),
+        help: Some("Build the package without `--frozen` and commit the updated build and outputs directories.".to_string()),
+    }
+
+    @backtraced
+    bundle_not_built {
+        args: (path: impl Display),
+        msg: format!("There is no build to bundle, as `{path}` doesn't exist."),
+        help: Some("Build the package with `leo build` before bundling it.".to_string()),
+    }
+
+    @backtraced
+    failed_to_write_bundle {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to write the bundle `{path}`: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    invalid_bundle {
+        args: (path: impl Display, reason: impl Display),
+        msg: format!("`{path}` is not a valid bundle: {reason}."),
+        help: Some("Bundles are written by `leo bundle`, and must be extracted unmodified.".to_string()),
+    }
+
+    @backtraced
+    invalid_manifest_path {
+        args: (path: impl Display, reason: impl Display),
+        msg: format!("`--manifest-path` names `{path}`, but {reason}."),
+        help: Some("`--manifest-path` takes the `program.json` of a package.".to_string()),
+    }
+
+    @backtraced
+    invalid_structured_input {
+        args: (path: impl Display, reason: impl Display),
+        msg: format!("Failed to read the input file `{path}`: {reason}."),
+        help: Some("The input file holds an object of the inputs of each transition, by name, as described by `leo schema`.".to_string()),
+    }
+
+    @backtraced
+    mismatched_structured_input {
+        args: (name: impl Display, function: impl Display, expected: impl Display, value: impl Display),
+        msg: format!("The input `{name}` of `{function}` is a `{expected}`, but its value is `{value}`."),
+        help: Some(format!("Write the value as a `{expected}` literal, e.g. as a string with its type suffix.")),
+    }
+
+    @backtraced
+    circuit_file_round_trip_mismatch {
+        args: (path: impl Display),
+        msg: format!("The circuit file `{path}` does not read back as the synthesized circuit."),
+        help: None,
+    }
+
+    @backtraced
+    unknown_only_function {
+        args: (name: impl Display, program: impl Display, functions: impl Display),
+        msg: format!("`--only-function` names `{name}`, but `{program}` has no function of that name."),
+        help: Some(format!("The functions of `{program}` are {functions}.")),
+    }
+
+    @backtraced
+    no_source_files {
+        args: (directory: impl Display),
+        msg: format!("There is nothing to build: `{directory}` has neither a `main.leo` nor a `lib.leo`."),
+        help: Some("Write the program of the package to `main.leo`, or a library to `lib.leo`. Another Leo file is built with `--entry`.".to_string()),
+    }
+
+    @backtraced
+    failed_to_synthesize_witness {
+        args: (function: impl Display, error: impl Display),
+        msg: format!("Failed to synthesize the witness of `{function}` on the input file.\nSnarkVM Error: {error}"),
+        help: Some("The records of the input file must be owned by the account of the `PRIVATE_KEY` in the `.env` file of the package.".to_string()),
+    }
+
+    @backtraced
+    frozen_without_build {
+        args: (file_name: impl Display),
+        msg: format!("`--frozen` forbids building '{file_name}', which has no previous build to compare against."),
+        help: Some("Build the package without `--frozen` and commit the build and outputs directories.".to_string()),
+    }
 );
+
+impl CliError {
+    /// Returns the status the process exits with for the CLI error of the given exit code, which is that of the
+    /// class of the error, see the exit statuses in the error index. It is computed from the exit code alone, as
+    /// the error itself is gone once the handler has emitted it.
+    pub fn exit_status_of(exit_code: i32) -> i32 {
+        match exit_code % 1_000 {
+            // A file of the package, such as the manifest or an input file, is missing or can't be read or written.
+            0 | 20 | 22 | 23 | 25 | 43 | 47 | 50 | 51 | 59 | 61 | 63 | 68 | 70 | 75 => 20,
+            // The package doesn't compile, or compiles to a program which the build rejects.
+            8 | 24 | 35 | 36 | 49 | 52 | 62 | 66 | 77 => 21,
+            // A written AVM or circuit file doesn't read back as what was written.
+            21 | 73 => 22,
+            // The build took longer than `--timeout`.
+            46 => 23,
+            _ => 17,
+        }
+    }
+}
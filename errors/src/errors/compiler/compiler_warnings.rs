@@ -0,0 +1,34 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+
+use std::fmt::Display;
+
+create_messages!(
+    /// CompilerWarning enum that represents all the warnings for the `leo-compiler` crate.
+    CompilerWarning,
+    code_mask: 6000i32,
+    code_prefix: "CMP",
+
+    /// For when an imported program is never referenced.
+    @formatted
+    unused_import {
+        args: (name: impl Display),
+        msg: format!("The import `{name}.leo` is never used."),
+        help: Some("Remove the import statement.".to_string()),
+    }
+);
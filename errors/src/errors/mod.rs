@@ -128,7 +128,32 @@ impl LeoError {
             LoopUnrollerError(error) => error.exit_code(),
             FlattenError(error) => error.exit_code(),
             LastErrorCode(code) => *code,
-            Anyhow(_) => unimplemented!(), // todo: implement exit codes for snarkvm errors.
+            Anyhow(_) => 1,
+        }
+    }
+
+    /// Returns the status the process exits with, which is the same for all errors of a type.
+    /// Unlike the exit code, it fits in the 8 bits of an exit status, see the exit statuses in the error index.
+    pub fn exit_status(&self) -> i32 {
+        exit_status(self.exit_code())
+    }
+
+    /// Renders the error as a line of JSON.
+    pub fn to_json(&self) -> String {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.to_json(),
+            CompilerError(error) => error.to_json(),
+            CliError(error) => error.to_json(),
+            InputError(error) => error.to_json(),
+            ParserError(error) => error.to_json(),
+            PackageError(error) => error.to_json(),
+            TypeCheckerError(error) => error.to_json(),
+            LoopUnrollerError(error) => error.to_json(),
+            FlattenError(error) => error.to_json(),
+            LastErrorCode(_) => unreachable!(),
+            Anyhow(error) => crate::uncoded_diagnostic_json(error.to_string()),
         }
     }
 }
@@ -140,6 +165,9 @@ pub enum LeoWarning {
     /// Represents an Parser Error in a Leo Error.
     #[error(transparent)]
     ParserWarning(#[from] ParserWarning),
+    /// Represents a Compiler Warning in a Leo Warning.
+    #[error(transparent)]
+    CompilerWarning(#[from] CompilerWarning),
 }
 
 impl LeoWarning {
@@ -149,9 +177,32 @@ impl LeoWarning {
 
         match self {
             ParserWarning(warning) => warning.warning_code(),
+            CompilerWarning(warning) => warning.warning_code(),
+        }
+    }
+
+    /// Renders the warning as a line of JSON.
+    pub fn to_json(&self) -> String {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.to_json(),
+            CompilerWarning(warning) => warning.to_json(),
         }
     }
 }
 
 /// A global result type for all Leo crates, that defaults the errors to be a LeoError.
 pub type Result<T, E = LeoError> = core::result::Result<T, E>;
+
+/// Returns the exit status of the given exit code, which is `10` plus the thousands digit of the code,
+/// e.g. `15` for the package errors, which have exit codes from `375_000` to `375_999`.
+/// The CLI errors, from `377_000` to `377_999`, have a status per class of error, such as `23` for a timeout.
+/// Exit codes below `1_000`, such as the `1` of snarkVM errors, are used as they are.
+pub fn exit_status(exit_code: i32) -> i32 {
+    match exit_code {
+        0..=999 => exit_code,
+        _ if exit_code % 10_000 / 1_000 == 7 => CliError::exit_status_of(exit_code),
+        _ => 10 + exit_code % 10_000 / 1_000,
+    }
+}
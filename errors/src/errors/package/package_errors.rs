@@ -313,4 +313,188 @@ create_messages!(
         msg: format!("IO error env file from the provided file path - {error}"),
         help: None,
     }
+
+    /// For when the program statistics file has an IO error.
+    @backtraced
+    io_error_stats_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error program statistics file from the provided file path - {error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_read_directory {
+        args: (dirname: impl Display, error: impl ErrorArg),
+        msg: format!("failed to read directory: {dirname}, error: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_remove_file {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("failed to remove file: {path}, error: {error}"),
+        help: None,
+    }
+
+    @backtraced
+    invalid_program_name {
+        args: (name: impl Display, reason: impl Display),
+        msg: format!("The program name `{name}` in `program.json` is invalid, as {reason}."),
+        help: Some("Program names can only contain ASCII alphanumeric characters and underscores, and must begin with a letter.".to_string()),
+    }
+
+    @backtraced
+    failed_to_read_ast_cache_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read the AST cache file from the provided file path - {path:?}"),
+        help: None,
+    }
+
+    @backtraced
+    io_error_ast_cache_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error AST cache file from the provided file path - {error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_remove_ast_cache_file {
+        args: (path: impl Debug),
+        msg: format!("failed removing the AST cache file from the provided file path - {path:?}"),
+        help: None,
+    }
+
+    @backtraced
+    io_error_report_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error program report file from the provided file path - {error}"),
+        help: None,
+    }
+
+    @backtraced
+    io_error_proof_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error proof file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the lib file has an IO error.
+    @backtraced
+    io_error_lib_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error lib file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the outputs directory can't be created.
+    @backtraced
+    failed_to_create_outputs_directory {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("failed creating outputs directory `{path}` - {error}"),
+        help: None,
+    }
+
+    /// For when a file which is not a directory is in the way of the outputs directory.
+    @backtraced
+    outputs_path_is_not_a_directory {
+        args: (path: impl Display),
+        msg: format!("cannot create the outputs directory `{path}`, as a file which is not a directory exists at its path"),
+        help: Some("Remove or rename the file, or write the outputs elsewhere with `--output-dir`.".to_string()),
+    }
+
+    /// For when the build manifest file cannot be read.
+    @backtraced
+    failed_to_read_build_manifest_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read build manifest file from the provided file path - {path:?}"),
+        help: None,
+    }
+
+    /// For when the build manifest file has an IO error.
+    @backtraced
+    io_error_build_manifest_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error build manifest file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the build manifest file cannot be removed.
+    @backtraced
+    failed_to_remove_build_manifest_file {
+        args: (path: impl Debug),
+        msg: format!("failed removing build manifest file from the provided file path - {path:?}"),
+        help: None,
+    }
+
+    /// For when an artifact listed in the build manifest cannot be read.
+    @backtraced
+    failed_to_read_build_artifact {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("failed reading the build artifact `{path}` - {error}"),
+        help: None,
+    }
+
+    /// For when the input schema file has an IO error.
+    @backtraced
+    io_error_input_schema_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error input schema file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the leoignore file has an IO error.
+    @backtraced
+    io_error_leoignore_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error leoignore file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the dependency graph file has an IO error.
+    @backtraced
+    io_error_depgraph_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error dependency graph file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the constraint report file has an IO error.
+    @backtraced
+    io_error_constraint_report_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error constraint report file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the witness file has an IO error.
+    @backtraced
+    io_error_witness_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error witness file from the provided file path - {error}"),
+        help: None,
+    }
+
+    /// For when the circuit file is not a serialized circuit.
+    @backtraced
+    failed_to_parse_circuit_file {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to parse the circuit file - {error}"),
+        help: None,
+    }
+
+    /// For when the circuits of a circuit file don't match its checksum.
+    @backtraced
+    circuit_checksum_mismatch {
+        args: (program: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("The circuit file of `{program}` is corrupted: its checksum is `{expected}`, but its circuits hash to `{found}`."),
+        help: Some("The circuit file was changed after it was written. Rebuild the package to write it again.".to_string()),
+    }
+
+    /// For when the manifest is not valid JSON, or its sections have the wrong types.
+    @backtraced
+    failed_to_parse_manifest {
+        args: (path: impl Display, line: impl Display, column: impl Display, error: impl Display, snippet: impl Display),
+        msg: format!("Failed to parse the manifest `{path}` at line {line}, column {column}: {error}\n{snippet}"),
+        help: Some("The manifest is a JSON object, with its keys and strings in double quotes and no trailing commas.".to_string()),
+    }
 );
@@ -298,4 +298,11 @@ create_messages!(
         msg: format!("An array {kind} must have at least one element."),
         help: None,
     }
+
+    @backtraced
+    formatting_changed_tokens {
+        args: (),
+        msg: "Formatting the program would change more than its whitespace.",
+        help: Some("This is a bug in the formatter, the file has not been formatted.".to_string()),
+    }
 );
@@ -0,0 +1,258 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Extended documentation of error codes, printed by `leo explain`.
+
+/// The extended documentation of each explained error code, in the order of the codes.
+pub const EXPLANATIONS: &[(&str, &str)] = &[
+    (
+        "EPAR0370000",
+        "\
+An unexpected token was found while parsing.
+
+The parser expected one of the tokens listed in the message, but found another one. This is
+usually a missing `;`, `}` or `)`, or a keyword used as a name.
+
+    function foo() -> u8 {
+        return 1u8
+    }
+
+Add the missing token where the message points:
+
+    function foo() -> u8 {
+        return 1u8;
+    }
+",
+    ),
+    (
+        "EPAR0370003",
+        "\
+The file ended while the parser was in the middle of an item.
+
+This is usually an unclosed block: a `{` without its `}`, often in the scope of the program.
+Close every block that is opened before the end of the file.
+",
+    ),
+    (
+        "EPAR0370009",
+        "\
+A string other than the expected one was found.
+
+This is raised in places where the grammar allows a single word, such as the network of a program
+id, which must be `aleo`:
+
+    program hello.leo { ... }
+
+Use the expected word:
+
+    program hello.aleo { ... }
+",
+    ),
+    (
+        "ETYC0372003",
+        "\
+An expression has a type other than the one its context requires.
+
+Leo doesn't convert between types implicitly, so the literals and variables of an operation must
+all have the same type:
+
+    let a: u8 = 1u16;
+
+Give the literal the expected type, or convert it explicitly with `as`:
+
+    let a: u8 = 1u8;
+    let b: u8 = 1u16 as u8;
+",
+    ),
+    (
+        "ETYC0372005",
+        "\
+A name is used that doesn't refer to anything in scope.
+
+Variables must be defined before they are used, and structs, records, functions and mappings must
+be defined in the program or in one of its imports. Check the spelling of the name and that it is
+defined in the current scope.
+",
+    ),
+    (
+        "ETYC0372027",
+        "\
+A function has an annotation that Leo doesn't know.
+
+The only annotation is `@test`, which marks the transitions run by `leo test`. It isn't allowed on
+functions or inline functions:
+
+    @test
+    transition test_sum() {
+        assert_eq(sum(1u32, 2u32), 3u32);
+    }
+",
+    ),
+    (
+        "ETYC0372038",
+        "\
+A function with a return type has a path that doesn't return a value.
+
+Every branch of a function must end with a `return` unless the function returns `()`:
+
+    function abs(a: i8) -> i8 {
+        if a < 0i8 {
+            return -a;
+        }
+    }
+
+Return from the remaining branch:
+
+    function abs(a: i8) -> i8 {
+        if a < 0i8 {
+            return -a;
+        }
+        return a;
+    }
+",
+    ),
+    (
+        "EPAK0375029",
+        "\
+The manifest `program.json` couldn't be opened.
+
+Leo commands are run from the root of a package, which has a `program.json` next to its `src`
+directory. Run the command from the root of the package, pass its path with `--path`, or create a
+package with `leo new`.
+",
+    ),
+    (
+        "ECMP0376005",
+        "\
+The name of the program scope doesn't match the name of the package.
+
+The program in `src/main.leo` must be named after the `program` field of `program.json`. If the
+manifest has `\"program\": \"hello.aleo\"`, the program must be declared as:
+
+    program hello.aleo { ... }
+",
+    ),
+    (
+        "ECLI0377024",
+        "\
+The build emitted warnings, and warnings are denied.
+
+Warnings are turned into an error by `--deny-warnings`, or by `\"deny_warnings\": true` in the
+`compiler` section of `program.json`. Fix the warnings printed above the error, or allow them by
+dropping the option.
+",
+    ),
+    (
+        "ECLI0377043",
+        "\
+An import mapped in the `imports` section of `program.json` can't be read.
+
+Each import maps the name of a program to the path of its Aleo instructions:
+
+    \"imports\": {
+        \"credits.aleo\": \"imports/credits.aleo\"
+    }
+
+The file must exist, be named after the program it maps, and lie inside the import roots of the
+package.
+",
+    ),
+    (
+        "ECLI0377044",
+        "\
+One or more of the transitions annotated with `@test` failed.
+
+`leo test` runs each test transition and reports the ones whose assertions failed. The names of the
+failed tests are printed above the summary. Run a single test by passing its name as a filter:
+
+    leo test test_sum
+",
+    ),
+    (
+        "ECLI0377045",
+        "\
+The source of the program differs from the one of its last build.
+
+`leo checksum --verify` compares the checksum of `src/main.leo` to the one stored in
+`outputs/<program>.sum` by `leo build`. Rebuild the program, or check that the source wasn't
+modified since it was built.
+",
+    ),
+    (
+        "ECLI0377046",
+        "\
+The build took longer than the limit given with `--timeout`.
+
+The message names the phase the build was in when the limit was hit. The artifacts of the previous
+build are left intact. Raise the limit, or drop `--timeout` to build without one.
+",
+    ),
+    (
+        "ECLI0377047",
+        "\
+A function with parameters is run without inputs, and the package has no input file.
+
+Inputs are either passed after the name of the function:
+
+    leo run main 1u32 2u32
+
+or read from the section of the function in an input file in `inputs/`:
+
+    [main]
+    public a: u32 = 1u32;
+    b: u32 = 2u32;
+",
+    ),
+];
+
+/// Returns the extended documentation of the given error code, if it has one.
+/// The code is matched regardless of its case.
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS.iter().find(|(c, _)| c.eq_ignore_ascii_case(code)).map(|(_, explanation)| *explanation)
+}
+
+/// Returns the error codes that have an extended documentation.
+pub fn explained_codes() -> impl Iterator<Item = &'static str> {
+    EXPLANATIONS.iter().map(|(code, _)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CliError, LeoMessageCode};
+
+    #[test]
+    fn explained_codes_are_sorted_and_unique() {
+        let codes = explained_codes().collect::<Vec<_>>();
+        let mut sorted = codes.clone();
+        sorted.sort_by_key(|code| &code[4..]);
+        sorted.dedup();
+        assert_eq!(codes, sorted);
+    }
+
+    #[test]
+    fn explain_ignores_case() {
+        assert_eq!(explain("ecli0377047"), explain("ECLI0377047"));
+        assert_eq!(explain("ECLI0377047"), Some(EXPLANATIONS.last().unwrap().1));
+        assert!(explain("E0002").is_none());
+    }
+
+    #[test]
+    fn explained_code_matches_error() {
+        assert!(explain(&CliError::missing_input_file("main", 2).error_code()).is_some());
+        assert!(explain(&CliError::unknown_error_code("E0002", "").error_code()).is_none());
+    }
+}
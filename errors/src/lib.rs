@@ -29,6 +29,9 @@ pub use self::common::*;
 /// Contains traits and types for channels through which errors go.
 pub mod emitter;
 
+/// Contains the extended documentation of error codes.
+pub mod explain;
+
 /// Contains the errors and warnings for the Leo lang.
 pub mod errors;
 pub use self::errors::*;
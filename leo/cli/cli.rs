@@ -14,10 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::cli::{commands::*, context::*, helpers::*};
-use leo_errors::Result;
+use crate::cli::{
+    commands::*,
+    context::*,
+    helpers::{logger::LogFormat, *},
+};
+use leo_errors::{CliError, LeoError, Result};
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use colored::Colorize;
 use std::{path::PathBuf, process::exit};
 
@@ -28,14 +32,32 @@ pub struct CLI {
     #[clap(short, global = true, help = "Print additional information for debugging")]
     debug: bool,
 
-    #[clap(short, global = true, help = "Suppress CLI output")]
+    #[clap(short, long, global = true, help = "Suppress CLI output except for errors")]
     quiet: bool,
 
+    #[clap(short, long, global = true, action = clap::ArgAction::Count, help = "Print more information, repeat for more detail (-v: debug, -vv: trace)")]
+    verbose: u8,
+
     #[clap(subcommand)]
     command: Commands,
 
     #[clap(long, global = true, help = "Optional path to Leo program root folder")]
     path: Option<PathBuf>,
+
+    #[clap(long, global = true, value_enum, default_value_t, help = "The format of the log output")]
+    log_format: LogFormat,
+
+    #[clap(
+        long,
+        global = true,
+        env = "LEO_OFFLINE",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        help = "Forbids network access, failing any command which would fetch from the network"
+    )]
+    offline: bool,
+
+    #[clap(long, global = true, env = "LEO_JOBS", value_parser = clap::value_parser!(u16).range(1..), help = "The number of threads for parallel work, such as synthesizing keys and proving [default: the number of CPUs]")]
+    jobs: Option<u16>,
 }
 
 ///Leo compiler and package manager
@@ -56,11 +78,26 @@ enum Commands {
         #[clap(subcommand)]
         command: Example,
     },
-    #[clap(about = "Compile the current package as a program")]
+    #[clap(about = "Compile the current package as a program, without network access with `--offline`")]
     Build {
         #[clap(flatten)]
         command: Build,
     },
+    #[clap(about = "Check the current package for errors without generating Aleo instructions")]
+    Check {
+        #[clap(flatten)]
+        command: Check,
+    },
+    #[clap(about = "Print or verify the checksum of the current program")]
+    Checksum {
+        #[clap(flatten)]
+        command: Checksum,
+    },
+    #[clap(about = "Format the Leo source files of the current package")]
+    Fmt {
+        #[clap(flatten)]
+        command: Format,
+    },
     #[clap(about = "Clean the output directory")]
     Clean {
         #[clap(flatten)]
@@ -76,31 +113,123 @@ enum Commands {
         #[clap(flatten)]
         command: Execute,
     },
+    #[clap(about = "Synthesize the proving and verifying keys of the current package")]
+    Setup {
+        #[clap(flatten)]
+        command: Setup,
+    },
+    #[clap(about = "Prove the execution of a function with the synthesized proving keys")]
+    Prove {
+        #[clap(flatten)]
+        command: Prove,
+    },
+    #[clap(about = "Run the transitions annotated with `@test`")]
+    Test {
+        #[clap(flatten)]
+        command: Test,
+    },
     #[clap(about = "Update the Leo CLI")]
     Update {
         #[clap(flatten)]
         command: Update,
     },
+    #[clap(about = "Compare the statistics of two builds")]
+    Diff {
+        #[clap(flatten)]
+        command: Diff,
+    },
+    #[clap(about = "Verify a proof written by `leo prove`")]
+    Verify {
+        #[clap(flatten)]
+        command: Verify,
+    },
+    #[clap(about = "Write a JSON Schema of the input file of the current package")]
+    Schema {
+        #[clap(flatten)]
+        command: Schema,
+    },
+    #[clap(about = "Print the extended documentation of an error code")]
+    Explain {
+        #[clap(flatten)]
+        command: Explain,
+    },
+    #[clap(about = "Print a summary of a compiled program")]
+    Inspect {
+        #[clap(flatten)]
+        command: Inspect,
+    },
+    #[clap(about = "Convert a compiled program between Aleo instructions and AVM bytecode")]
+    Convert {
+        #[clap(flatten)]
+        command: Convert,
+    },
+    #[clap(about = "Run the static checks of the current package before publishing it")]
+    Audit {
+        #[clap(flatten)]
+        command: Audit,
+    },
+    #[clap(about = "Pack the compiled programs, checksum, and circuit of the last build into a single file")]
+    Bundle {
+        #[clap(flatten)]
+        command: Bundle,
+    },
+    #[clap(about = "Extract a bundle written by `leo bundle`, checking its files against their checksums")]
+    Unbundle {
+        #[clap(flatten)]
+        command: Unbundle,
+    },
 }
 
 pub fn handle_error<T>(res: Result<T>) -> T {
     match res {
         Ok(t) => t,
         Err(err) => {
-            eprintln!("{err}");
-            exit(err.exit_code());
+            // The last error code is returned after its error has been printed.
+            if !matches!(err, LeoError::LastErrorCode(_)) {
+                eprintln!("{err}");
+            }
+            exit(err.exit_status());
         }
     }
 }
 
+impl CLI {
+    /// Parses the command line, reading the build options not given as flags from their `LEO_*` environment variables.
+    pub fn parse_with_env() -> Self {
+        let mut matches = BuildOptions::with_env_vars(Self::command()).get_matches();
+        Self::from_arg_matches_mut(&mut matches).unwrap_or_else(|error| error.format(&mut Self::command()).exit())
+    }
+}
+
 /// Run command with custom build arguments.
 pub fn run_with_args(cli: CLI) -> Result<()> {
-    if !cli.quiet {
-        // Init logger with optional debug flag.
-        logger::init_logger("leo", match cli.debug {
-            false => 1,
-            true => 2,
-        })?;
+    // Init logger with the requested verbosity. Errors are printed even if the output is suppressed.
+    logger::init_logger(
+        "leo",
+        match (cli.quiet, cli.debug, cli.verbose) {
+            (true, ..) => 0,
+            (false, false, 0) => 1,
+            (false, true, 0) | (false, _, 1) => 2,
+            _ => 3,
+        },
+        cli.log_format,
+    )?;
+
+    // The logs are written to stderr while a build prints its output to stdout, so that the output can be piped.
+    if let Commands::Build { command } = &cli.command {
+        logger::set_logs_to_stderr(command.writes_to_stdout());
+    }
+
+    // Network access is forbidden for the whole process, so that no command can fetch anything.
+    offline::set_offline(cli.offline);
+
+    // Bound the threads of the global thread pool, which runs all parallel work, most of it in snarkVM.
+    // Builds are sequential, so they are single-threaded even without a bound.
+    if let Some(jobs) = cli.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.into())
+            .build_global()
+            .map_err(CliError::failed_to_configure_jobs)?;
     }
 
     // Get custom root folder and create context for it.
@@ -126,10 +255,25 @@ pub fn run_with_args(cli: CLI) -> Result<()> {
 
             command.try_execute(context)
         }
+        Commands::Check { command } => command.try_execute(context),
+        Commands::Checksum { command } => command.try_execute(context),
         Commands::Clean { command } => command.try_execute(context),
+        Commands::Fmt { command } => command.try_execute(context),
         Commands::Example { command } => command.try_execute(context),
         Commands::Run { command } => command.try_execute(context),
         Commands::Execute { command } => command.try_execute(context),
+        Commands::Setup { command } => command.try_execute(context),
+        Commands::Prove { command } => command.try_execute(context),
+        Commands::Test { command } => command.try_execute(context),
         Commands::Update { command } => command.try_execute(context),
+        Commands::Diff { command } => command.try_execute(context),
+        Commands::Verify { command } => command.try_execute(context),
+        Commands::Schema { command } => command.try_execute(context),
+        Commands::Explain { command } => command.try_execute(context),
+        Commands::Inspect { command } => command.try_execute(context),
+        Commands::Convert { command } => command.try_execute(context),
+        Commands::Audit { command } => command.try_execute(context),
+        Commands::Bundle { command } => command.try_execute(context),
+        Commands::Unbundle { command } => command.try_execute(context),
     }
 }
@@ -0,0 +1,202 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::commands::build::{
+    check_constraints,
+    copy_directory,
+    import_files,
+    program_name,
+    resolve_import_paths,
+    source_files,
+};
+
+use leo_compiler::Compiler;
+use leo_package::{inputs::INPUTS_DIRECTORY_NAME, root::ManifestConfig};
+
+use snarkvm::{file::Manifest, package::Package};
+
+/// Run the static checks of the current package before publishing it, without writing to its outputs directory.
+#[derive(Parser, Debug)]
+pub struct Audit {
+    #[clap(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Audit {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Audit")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let package_path = context.dir()?;
+        let mut failures = Vec::new();
+        let mut report = |check: &'static str, result: Result<()>| match result {
+            Ok(()) => tracing::info!("✅ {check}"),
+            Err(error) => {
+                // The errors of the compiler have already been printed.
+                match error {
+                    LeoError::LastErrorCode(_) => tracing::error!("❌ {check}"),
+                    error => tracing::error!("❌ {check}: {error}"),
+                }
+                failures.push(check);
+            }
+        };
+
+        // The other checks read the manifest, so they can't run without it.
+        let config = ManifestConfig::read_from(&package_path).and_then(|config| {
+            Manifest::<CurrentNetwork>::open(&package_path).map_err(PackageError::failed_to_open_manifest)?;
+            Ok(config)
+        });
+        let config = match config {
+            Ok(config) => config,
+            Err(error) => {
+                report("manifest", Err(error));
+                return Err(CliError::audit_failed("manifest").into());
+            }
+        };
+        report("manifest", Ok(()));
+        report("program name", config.validate());
+        report("unused imports", check_unused_imports(&package_path, &config));
+
+        // The package is built in a copy, so that its build and outputs directories are left as they are.
+        // The copy is first built without its input files, so that a mismatched input file isn't reported as a
+        // compilation error.
+        let copy = std::env::temp_dir().join(format!("leo-audit-{}", std::process::id()));
+        let mut options = self.compiler_options.clone();
+        options.max_constraints = None;
+        options.output_dir = None;
+        options.git_ref = None;
+        let build = |options: &BuildOptions| {
+            let build = Build::new(options.clone());
+            // The hooks of the package are not run, as nothing is published by the audit.
+            build.build(Context::new(Some(copy.clone()))?, &Handler::default(), None)
+        };
+        let compiled = copy_package(&package_path, &copy).and_then(|_| build(&options).map(|_| ()));
+        let is_compiled = compiled.is_ok();
+        report("compilation", compiled);
+        if is_compiled {
+            let inputs_directory = package_path.join(INPUTS_DIRECTORY_NAME);
+            match inputs_directory.exists() {
+                true => report(
+                    "input file",
+                    copy_directory(&inputs_directory, &copy.join(INPUTS_DIRECTORY_NAME))
+                        .and_then(|_| build(&options).map(|_| ())),
+                ),
+                false => tracing::info!("➖ input file: skipped, as the package has no input files"),
+            }
+
+            let max_constraints = self.compiler_options.with_manifest_defaults(&config.compiler).max_constraints;
+            match max_constraints {
+                Some(max_constraints) => report(
+                    "constraint budget",
+                    (|| {
+                        let package = Package::<CurrentNetwork>::open(&copy.join(BUILD_DIRECTORY_NAME))
+                            .map_err(CliError::failed_to_execute_build)?;
+                        let mut process = package.get_process().map_err(CliError::failed_to_synthesize_keys)?;
+                        let rng = &mut self.compiler_options.rng();
+                        check_constraints(&mut process, package.program(), None, Some(max_constraints), rng, None)
+                            .map(|_| ())
+                    })(),
+                ),
+                None => tracing::info!("➖ constraint budget: skipped, as no budget is set by `--max-constraints`"),
+            }
+        }
+
+        // The copy is removed even if a check failed.
+        if copy.exists() {
+            std::fs::remove_dir_all(&copy).map_err(CliError::cli_io_error)?;
+        }
+
+        match failures.is_empty() {
+            true => {
+                tracing::info!("The package passed all checks");
+                Ok(())
+            }
+            false => Err(CliError::audit_failed(failures.join(", ")).into()),
+        }
+    }
+}
+
+/// Returns an error if a program of the package imports a program it doesn't use, printing the warnings for them.
+fn check_unused_imports(package_path: &Path, config: &ManifestConfig) -> Result<()> {
+    let manifest = Manifest::<CurrentNetwork>::open(package_path).map_err(PackageError::failed_to_open_manifest)?;
+    let program_id = manifest.program_id();
+    let source_directory = BuildOptions::default().source_directory(package_path, &config.build)?;
+    let import_paths = resolve_import_paths(package_path, &config.imports)?;
+    let import_files = import_files(package_path, &source_directory, &import_paths, false)?;
+    let source_files = source_files(package_path, &source_directory, false)?;
+
+    // The warnings are buffered, so that only those of unused imports are printed.
+    let (handler, buffer) = Handler::new_with_buf();
+    let files =
+        import_files.into_iter().map(|path| (path, true)).chain(source_files.into_iter().map(|path| (path, false)));
+    for (file_path, is_import) in files {
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(PackageError::failed_to_get_file_name)?
+            .to_string();
+        let mut compiler = Compiler::new(
+            program_name(&file_name, program_id, is_import)?,
+            program_id.network().to_string(),
+            &handler,
+            file_path,
+            package_path.join(OUTPUTS_DIRECTORY_NAME),
+            None,
+        )
+        .with_import_paths(import_paths.clone());
+        compiler.parse_program()?;
+        compiler.unused_import_pass();
+    }
+
+    let warnings = buffer.extract_warnings().into_inner();
+    for warning in &warnings {
+        eprintln!("{warning}");
+    }
+    match warnings.is_empty() {
+        true => Ok(()),
+        false => Err(CliError::unused_imports_found(warnings.len()).into()),
+    }
+}
+
+/// Copies the package to the given directory, without its build, outputs, and inputs directories.
+fn copy_package(package_path: &Path, copy: &Path) -> Result<()> {
+    std::fs::create_dir_all(copy).map_err(CliError::cli_io_error)?;
+    for entry in std::fs::read_dir(package_path).map_err(CliError::cli_io_error)? {
+        let path = entry.map_err(CliError::cli_io_error)?.path();
+        let name = path.file_name().unwrap_or_default();
+        if [BUILD_DIRECTORY_NAME, OUTPUTS_DIRECTORY_NAME, INPUTS_DIRECTORY_NAME, ".git"]
+            .iter()
+            .any(|directory| Path::new(directory) == Path::new(name))
+        {
+            continue;
+        }
+        let target = copy.join(name);
+        match path.is_dir() {
+            true => copy_directory(&path, &target)?,
+            false => std::fs::copy(&path, &target).map(|_| ()).map_err(CliError::cli_io_error)?,
+        }
+    }
+    Ok(())
+}
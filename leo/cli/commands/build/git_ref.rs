@@ -0,0 +1,80 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Building a package as it is at a git revision, for `leo build --git-ref`.
+
+use super::*;
+
+/// Builds the package as it is at the given git revision, from a copy of its files at that revision.
+/// The sources of the working tree are left untouched: the outputs and the compiled program are written to the outputs
+/// directory of the package, or to `--output-dir`, and the copy is always removed.
+pub(super) fn build_git_ref(build: Build, git_ref: &str, context: Context) -> Result<<Build as Command>::Output> {
+    let package_path = context.dir()?;
+    let git = |args: &[&str]| -> Result<Vec<u8>> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&package_path)
+            .args(args)
+            .output()
+            .map_err(|e| CliError::failed_to_read_git_ref(git_ref, e))?;
+        match output.status.success() {
+            true => Ok(output.stdout),
+            false => {
+                Err(CliError::failed_to_read_git_ref(git_ref, String::from_utf8_lossy(&output.stderr).trim()).into())
+            }
+        }
+    };
+
+    // The files are listed relative to the package, and read relative to the root of the repository.
+    let commit =
+        String::from_utf8_lossy(&git(&["rev-parse", "--verify", &format!("{git_ref}^{{commit}}")])?).trim().to_string();
+    let prefix = String::from_utf8_lossy(&git(&["rev-parse", "--show-prefix"])?).trim().to_string();
+    let files = git(&["ls-tree", "-r", "-z", "--name-only", &commit, "--", "."])?;
+    tracing::info!("Building the package at '{git_ref}' ({})", &commit[..commit.len().min(12)]);
+
+    let tree = std::env::temp_dir().join(format!("leo-git-ref-{commit}-{}", std::process::id()));
+    let output_dir = build.options.outputs_directory(&package_path)?;
+    let result = (|| {
+        for file in files.split(|byte| *byte == 0).filter(|file| !file.is_empty()) {
+            let file = String::from_utf8_lossy(file);
+            let contents = git(&["show", &format!("{commit}:{prefix}{file}")])?;
+            let path = tree.join(file.as_ref());
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(CliError::cli_io_error)?;
+            }
+            std::fs::write(&path, contents).map_err(CliError::cli_io_error)?;
+        }
+
+        let mut options = build.options.clone();
+        options.git_ref = None;
+        options.output_dir = Some(output_dir.clone());
+        let output = Build { options, ..build }.apply(Context::new(Some(tree.clone()))?, ())?;
+
+        // The compiled program is written beside the outputs, as for a read-only package.
+        let build_directory = output_dir.join(BUILD_DIRECTORY_NAME);
+        if build_directory.exists() {
+            std::fs::remove_dir_all(&build_directory).map_err(CliError::cli_io_error)?;
+        }
+        copy_directory(&tree.join(BUILD_DIRECTORY_NAME), &build_directory)?;
+        Ok(output)
+    })();
+
+    // The copy is removed whether or not the build succeeded, as its artifacts were copied out of it.
+    if tree.exists() {
+        std::fs::remove_dir_all(&tree).map_err(CliError::cli_io_error)?;
+    }
+    result
+}
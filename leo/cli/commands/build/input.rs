@@ -0,0 +1,207 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reading input files: their includes, their environment variables, and inputs in JSON or TOML.
+
+use super::*;
+
+use leo_ast::{Function, Type, Variant};
+
+/// Replaces every line `include "FILE";` of an input file with the contents of `FILE`, relative to the input file.
+/// Included files may include other files, and `stack` holds the files being included, to detect cycles.
+pub(crate) fn resolve_input_includes(input: &str, path: &Path, stack: &mut Vec<PathBuf>) -> Result<String> {
+    let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if let Some(start) = stack.iter().position(|included| included == &canonical_path) {
+        let cycle = stack[start..].iter().chain([&canonical_path]).map(|path| format!("`{}`", path.display()));
+        return Err(CliError::input_include_cycle(cycle.collect::<Vec<_>>().join(" -> ")).into());
+    }
+    stack.push(canonical_path);
+
+    let mut resolved = String::with_capacity(input.len());
+    for line in input.split_inclusive('\n') {
+        let included = line
+            .trim()
+            .strip_prefix("include ")
+            .map(|rest| rest.trim().trim_end_matches(';').trim_end())
+            .and_then(|file| file.strip_prefix('"'))
+            .and_then(|file| file.strip_suffix('"'));
+        match included {
+            Some(file) => {
+                let included_path = path.parent().unwrap_or(Path::new("")).join(file);
+                let contents = std::fs::read_to_string(&included_path)
+                    .map_err(|e| CliError::input_include_not_found(included_path.display(), path.display(), e))?;
+                resolved.push_str(&resolve_input_includes(&contents, &included_path, stack)?);
+                if !resolved.ends_with('\n') {
+                    resolved.push('\n');
+                }
+            }
+            None => resolved.push_str(line),
+        }
+    }
+
+    stack.pop();
+    Ok(resolved)
+}
+
+/// Returns the transitions declared in the given Leo files, by name.
+pub(super) fn transitions(file_paths: &[PathBuf]) -> Result<IndexMap<String, Function>> {
+    let mut transitions = IndexMap::new();
+    for file_path in file_paths {
+        let source = std::fs::read_to_string(file_path).map_err(|e| CompilerError::file_read_error(file_path, e))?;
+        let sf = with_session_globals(|s| s.source_map.new_source(&source, FileName::Real(file_path.clone())));
+        let ast = leo_parser::parse_ast(&Handler::default(), &NodeBuilder::default(), &sf.src, sf.start_pos)?;
+        for scope in ast.into_repr().program_scopes.into_values() {
+            transitions.extend(
+                scope
+                    .functions
+                    .into_iter()
+                    .filter(|(_, function)| matches!(function.variant, Variant::Transition))
+                    .map(|(name, function)| (name.to_string(), function)),
+            );
+        }
+    }
+    Ok(transitions)
+}
+
+/// A value of an input file in JSON or TOML.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StructuredValue {
+    Boolean(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Literal(String),
+}
+
+/// Translates an input file in JSON or TOML into the `.in` format.
+/// The input file maps the name of each transition to its inputs by name, which are written in the order of the
+/// parameters of the transition, so that each one is assigned to the register of its parameter.
+/// The values of literal types are checked against the types of the parameters, and may be written without their
+/// type suffix; the other values are Leo expressions, which are checked once the input file is parsed.
+pub(crate) fn translate_structured_input(
+    input: &str,
+    format: InputFormat,
+    path: &Path,
+    transitions: &IndexMap<String, Function>,
+) -> Result<String> {
+    let invalid = |reason: String| CliError::invalid_structured_input(path.display(), reason);
+    let sections: IndexMap<String, IndexMap<String, StructuredValue>> = match format {
+        InputFormat::Json => serde_json::from_str(input).map_err(|e| invalid(e.to_string()))?,
+        InputFormat::Toml => toml::from_str(input).map_err(|e| invalid(e.message().to_string()))?,
+        InputFormat::Leo => return Ok(input.to_string()),
+    };
+
+    let mut translated = String::new();
+    for (function_name, mut values) in sections {
+        let function = transitions
+            .get(&function_name)
+            .ok_or_else(|| invalid(format!("`{function_name}` is not a transition of the program")))?;
+        translated += &format!("[{function_name}]\n");
+        for parameter in function.input.iter() {
+            let name = parameter.identifier().to_string();
+            let value = values
+                .shift_remove(&name)
+                .ok_or_else(|| invalid(format!("the input `{name}` of `{function_name}` is missing")))?;
+            let type_ = parameter.type_();
+            let value = structured_value(value, &type_)
+                .map_err(|value| CliError::mismatched_structured_input(&name, &function_name, &type_, value))?;
+            let mode = match parameter.mode().to_string() {
+                mode if mode.is_empty() => mode,
+                mode => format!("{mode} "),
+            };
+            // Booleans are displayed as `boolean`, but written as `bool` in input files.
+            let type_ = match type_ {
+                Type::Boolean => "bool".to_string(),
+                type_ => type_.to_string(),
+            };
+            translated += &format!("{mode}{name}: {type_} = {value};\n");
+        }
+        if let Some(name) = values.keys().next() {
+            return Err(invalid(format!("`{name}` is not an input of `{function_name}`")).into());
+        }
+    }
+    Ok(translated)
+}
+
+/// Returns the Leo expression of a value of an input file in JSON or TOML, given the type of its parameter.
+/// Fails with the value as it was written if it isn't of the type.
+fn structured_value(value: StructuredValue, type_: &Type) -> std::result::Result<String, String> {
+    let suffix = match type_ {
+        Type::Integer(integer_type) => Some(integer_type.to_string()),
+        Type::Field => Some("field".to_string()),
+        Type::Group => Some("group".to_string()),
+        Type::Scalar => Some("scalar".to_string()),
+        _ => None,
+    };
+    let is_number = |value: &str| {
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '_')
+    };
+    match (value, type_, suffix) {
+        (StructuredValue::Boolean(value), Type::Boolean, _) => Ok(value.to_string()),
+        (StructuredValue::Boolean(value), _, _) => Err(value.to_string()),
+        (StructuredValue::Unsigned(value), _, Some(suffix)) => Ok(format!("{value}{suffix}")),
+        (StructuredValue::Signed(value), _, Some(suffix)) => Ok(format!("{value}{suffix}")),
+        (StructuredValue::Unsigned(value), _, None) => Err(value.to_string()),
+        (StructuredValue::Signed(value), _, None) => Err(value.to_string()),
+        (StructuredValue::Literal(value), Type::Boolean, _) if value == "true" || value == "false" => Ok(value),
+        (StructuredValue::Literal(value), Type::Address, _) if value.starts_with("aleo1") => Ok(value),
+        (StructuredValue::Literal(value), Type::Boolean | Type::Address, _) => Err(value),
+        (StructuredValue::Literal(value), _, Some(suffix)) => match value.strip_suffix(suffix.as_str()) {
+            Some(number) if is_number(number) => Ok(value),
+            _ if is_number(&value) => Ok(format!("{value}{suffix}")),
+            _ => Err(value),
+        },
+        (StructuredValue::Literal(value), _, None) => Ok(value),
+    }
+}
+
+/// Replaces every `${NAME}` in an input file with the value of the variable `NAME` looked up by `var`, which reads
+/// the environment in a build. Returns the substituted input with the inputs read from variables, as
+/// `[function] name` and the variable.
+pub(crate) fn substitute_env_vars(
+    input: &str,
+    path: &Path,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<(String, Vec<(String, String)>)> {
+    let mut substituted = String::with_capacity(input.len());
+    let mut env_inputs = Vec::new();
+    let mut section = "";
+    for line in input.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed.trim_start_matches('[').split(']').next().unwrap_or_default();
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find("${") {
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+                break;
+            };
+            let variable = &rest[start + 2..end];
+            let value = var(variable).ok_or_else(|| CliError::undefined_input_variable(variable, path.display()))?;
+            // The name of the input is the last word before the type, after its mode.
+            let name = trimmed.split(':').next().and_then(|name| name.split_whitespace().last()).unwrap_or_default();
+            env_inputs.push((format!("[{section}] {name}"), variable.to_string()));
+
+            substituted.push_str(&rest[..start]);
+            substituted.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        substituted.push_str(rest);
+    }
+    Ok((substituted, env_inputs))
+}
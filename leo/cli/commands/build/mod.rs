@@ -0,0 +1,1976 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+mod git_ref;
+use git_ref::build_git_ref;
+
+pub(crate) mod input;
+use input::{resolve_input_includes, substitute_env_vars, transitions, translate_structured_input};
+
+mod synthesis;
+pub(crate) use synthesis::check_constraints;
+use synthesis::{constraint_report, serialize_circuit, serialize_witness, witness_signer};
+
+mod watch;
+use watch::watch;
+
+pub(crate) mod workspace;
+use workspace::build_workspace;
+
+use super::*;
+use crate::cli::helpers::timeout::Timeout;
+
+use leo_ast::{Ast, NodeBuilder, Program as AstProgram, Struct};
+use leo_compiler::{Compiler, CompilerOptions, InputAst, OutputOptions};
+use leo_package::{
+    GZIP_FILE_EXTENSION,
+    LEO_FILE_EXTENSION,
+    build::{BUILD_DIRECTORY_NAME, BuildDirectory},
+    imports::{IMPORTS_DIRECTORY_NAME, ImportsDirectory},
+    inputs::{INPUT_FILE_EXTENSION, INPUTS_DIRECTORY_NAME, InputFile, InputsDirectory, STATE_FILE_EXTENSION},
+    outputs::{
+        AstCacheFile,
+        BuildArtifact,
+        BuildManifestFile,
+        CHECKSUM_FILE_EXTENSION,
+        CIRCUIT_FILE_EXTENSION,
+        COMPILER_VERSION_PREFIX,
+        ChecksumAlgorithm,
+        ChecksumFile,
+        CircuitFile,
+        ConstraintReportFile,
+        DepgraphFile,
+        MAIN_ALEO_FILE_NAME,
+        OutputsDirectory,
+        ProofFile,
+        ReportFile,
+        SerializedCircuit,
+        StatsFile,
+        WitnessFile,
+    },
+    root::{BinConfig, ImportsConfig, LEOIGNORE_FILENAME, Leoignore, MANIFEST_FILENAME, ManifestConfig},
+    source::{LIB_FILENAME, MAIN_FILENAME, SourceDirectory},
+    write_atomically,
+};
+use leo_span::{
+    Symbol,
+    source_map::FileName,
+    sym,
+    symbol::{create_session_if_not_set_then, with_session_globals},
+};
+
+use snarkvm::{
+    file::{AVMFile, Manifest},
+    package::Package,
+    prelude::{FromBytes, Identifier, Process, Program, ProgramID, Testnet3, ToBytes, ValueType},
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// The name of the source of a program read from stdin by `leo build --stdin`.
+const STDIN_FILE_NAME: &str = "<stdin>";
+
+/// The version of the AST cache format.
+/// Caches written with another format version, or by another compiler version, are ignored.
+const AST_CACHE_FORMAT_VERSION: u32 = 1;
+
+/// The line following the checksums of a program built with dead code elimination.
+const DCE_CHECKSUM_LINE: &str = "dce";
+
+impl From<BuildOptions> for CompilerOptions {
+    fn from(options: BuildOptions) -> Self {
+        let mut out_options = Self {
+            build: leo_compiler::BuildOptions { dce_enabled: options.enable_dce },
+            output: OutputOptions {
+                symbol_table_spans_enabled: options.enable_symbol_table_spans,
+                initial_symbol_table: options.enable_initial_symbol_table_snapshot,
+                type_checked_symbol_table: options.enable_type_checked_symbol_table_snapshot,
+                unrolled_symbol_table: options.enable_unrolled_symbol_table_snapshot,
+                ast_spans_enabled: options.enable_ast_spans,
+                initial_input_ast: options.enable_initial_input_ast_snapshot,
+                initial_ast: options.enable_initial_ast_snapshot,
+                unrolled_ast: options.enable_unrolled_ast_snapshot,
+                ssa_ast: options.enable_ssa_ast_snapshot,
+                flattened_ast: options.enable_flattened_ast_snapshot,
+                destructured_ast: options.enable_destructured_ast_snapshot,
+                inlined_ast: options.enable_inlined_ast_snapshot,
+                dce_ast: options.enable_dce_ast_snapshot,
+            },
+        };
+        if options.enable_all_ast_snapshots {
+            out_options.output.initial_input_ast = true;
+            out_options.output.initial_ast = true;
+            out_options.output.unrolled_ast = true;
+            out_options.output.ssa_ast = true;
+            out_options.output.flattened_ast = true;
+            out_options.output.destructured_ast = true;
+            out_options.output.inlined_ast = true;
+            out_options.output.dce_ast = true;
+        }
+
+        out_options
+    }
+}
+
+/// The time spent in each phase of a build, and the size of what it compiled.
+#[derive(Clone, Debug, Default)]
+pub struct BuildTimings {
+    /// Compiling the programs in the `imports/` directory.
+    pub imports: Duration,
+    /// Compiling the programs in the `src/` directory.
+    pub sources: Duration,
+    /// Parsing the input file.
+    pub input: Duration,
+    /// Checking the compiled Aleo programs with snarkVM.
+    pub package: Duration,
+    /// The number of non-blank lines of the Leo files compiled by the build, excluding those that were up to date.
+    pub lines: usize,
+    /// The size in bytes of the Aleo instructions compiled by the build, excluding the programs that were up to date.
+    pub program_size: usize,
+    /// The number of constraints of the functions of the program, if their circuits were synthesized.
+    pub constraints: Option<u64>,
+    /// The peak resident set size of the process in bytes, with `--report-memory` on platforms reporting it.
+    pub peak_memory: Option<u64>,
+}
+
+/// The output of the build command.
+#[derive(Clone, Debug)]
+pub struct BuildOutput {
+    /// The AST of the input file, if it exists.
+    pub input_ast: Option<InputAst>,
+    /// The struct declarations of the compiled programs.
+    pub structs: IndexMap<Symbol, Struct>,
+    /// The names of the transitions annotated with `@test` in the source files.
+    pub tests: Vec<String>,
+    /// The time spent in each phase of the build.
+    pub timings: BuildTimings,
+    /// Whether the build wrote its artifacts, which it does unless it is a dry run.
+    pub persisted: bool,
+    /// Whether any program was compiled, rather than reused from the previous build.
+    pub recompiled: bool,
+    /// The outputs of the entry files in the `bins` section of the manifest, by name, if it has any.
+    /// The other fields are those of the last entry built, with the structs and tests of all of them.
+    pub bins: IndexMap<String, BuildOutput>,
+}
+
+/// Compile and build program command.
+#[derive(Parser, Debug, Default)]
+pub struct Build {
+    #[clap(flatten)]
+    pub(crate) options: BuildOptions,
+    #[clap(
+        long,
+        conflicts_with = "git_ref",
+        help = "Rebuilds the package whenever a source, import, or input file changes."
+    )]
+    pub(crate) watch: bool,
+    #[clap(long, help = "Compiles the package without writing any files, logging the files that would be written.")]
+    pub(crate) dry_run: bool,
+    #[clap(
+        long,
+        conflicts_with = "git_ref",
+        help = "Builds every member of the workspace, in the order in which they import each other."
+    )]
+    pub(crate) workspace: bool,
+    #[clap(
+        long,
+        requires = "workspace",
+        help = "Builds the remaining workspace members after a member fails, and reports every failure at the end."
+    )]
+    pub(crate) keep_going: bool,
+    #[clap(
+        long,
+        value_name = "REV",
+        requires = "workspace",
+        help = "Builds only the workspace members whose files changed since the given git revision, and the members importing them. Builds every member if git can't tell what changed."
+    )]
+    pub(crate) since: Option<String>,
+    #[clap(
+        long,
+        value_name = "FILE",
+        conflicts_with = "stdin",
+        help = "Builds the package of the given `program.json`, resolving all of its paths against the directory of the manifest rather than the current directory."
+    )]
+    pub(crate) manifest_path: Option<PathBuf>,
+    #[clap(
+        long,
+        conflicts_with_all = ["watch", "workspace", "git_ref"],
+        help = "Compiles a program read from stdin, without a package, and logs the number of constraints of its functions."
+    )]
+    pub(crate) stdin: bool,
+}
+
+impl Command for Build {
+    type Input = ();
+    type Output = BuildOutput;
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // With `--manifest-path`, the package is the directory of the manifest, whatever the current directory.
+        let context = match &self.manifest_path {
+            Some(manifest_path) => Context::new(Some(manifest_directory(manifest_path)?))?,
+            None => context,
+        };
+        if let Some(git_ref) = self.options.git_ref.clone() {
+            return build_git_ref(self, &git_ref, context);
+        }
+        if self.watch {
+            return watch(self.options, self.dry_run, context);
+        }
+        if self.workspace {
+            return build_workspace(self.options, self.dry_run, self.keep_going, self.since.as_deref(), context);
+        }
+
+        // Initialize error handler, which prints the compiler errors and warnings in the requested format.
+        let sarif = SarifEmitter::new();
+        let handler = self.options.message_format.handler(&sarif);
+
+        // With `--timeout`, the build fails if it takes too long, reporting how far it got.
+        let timeout = self.options.timeout.map(Timeout::start);
+        let output = match self.stdin {
+            true => build_stdin(&self.options, &handler, timeout.as_ref()),
+            false => self.build(context.clone(), &handler, timeout.as_ref()),
+        };
+        let output = match output {
+            // Errors outside the compiler are printed through the handler as well, so that all of them are JSON.
+            Err(err)
+                if self.options.message_format == MessageFormat::Json && !matches!(err, LeoError::LastErrorCode(_)) =>
+            {
+                let code = err.exit_code();
+                handler.emit_err(err);
+                Err(LeoError::LastErrorCode(code))
+            }
+            output => output,
+        };
+
+        // The SARIF log only holds the messages of the compiler, which point to source lines.
+        // Other errors are only printed, but still fail the build.
+        if let (MessageFormat::Sarif, Some(path)) = (self.options.message_format, &self.options.sarif_out) {
+            write_atomically(path, sarif.to_sarif().as_bytes()).map_err(CliError::failed_to_write_file)?;
+        }
+
+        // The post-build hook only runs after a build which wrote a newly compiled program to the package.
+        if let (Ok(output), false) = (&output, self.stdin) {
+            if output.persisted && output.recompiled {
+                run_post_build_hook(&context.dir()?, &self.options, output)?;
+            }
+        }
+        output
+    }
+}
+
+/// Runs the post-build hook given with `--post-build`, or otherwise in the `hooks` section of the manifest.
+/// The hook is run in the package directory by the shell, with the package, its compiled program, and its number
+/// of constraints, if they were counted, in the environment variables `LEO_PACKAGE`, `LEO_PROGRAM`, and
+/// `LEO_CONSTRAINTS`.
+fn run_post_build_hook(package_path: &Path, options: &BuildOptions, output: &BuildOutput) -> Result<()> {
+    let config = match package_path.join(MANIFEST_FILENAME).exists() {
+        true => ManifestConfig::read_from(package_path)?,
+        false => ManifestConfig::synthetic(package_path)?,
+    };
+    let Some(hook) = options.post_build.as_ref().or(config.hooks.post_build.as_ref()) else {
+        return Ok(());
+    };
+
+    tracing::info!("Running the post-build hook `{hook}`");
+    let mut command = match cfg!(windows) {
+        true => std::process::Command::new("cmd"),
+        false => std::process::Command::new("sh"),
+    };
+    command
+        .arg(if cfg!(windows) { "/C" } else { "-c" })
+        .arg(hook)
+        .current_dir(package_path)
+        .env("LEO_PACKAGE", config.program.strip_suffix(".aleo").unwrap_or(&config.program))
+        .env("LEO_PROGRAM", options.build_root(package_path)?.join(BUILD_DIRECTORY_NAME).join(MAIN_ALEO_FILE_NAME));
+    if let Some(constraints) = output.timings.constraints {
+        command.env("LEO_CONSTRAINTS", constraints.to_string());
+    }
+
+    let error = match command.status() {
+        Ok(status) if status.success() => return Ok(()),
+        Ok(status) => status.to_string(),
+        Err(error) => error.to_string(),
+    };
+    match options.hook_best_effort {
+        true => {
+            tracing::warn!("The post-build hook `{hook}` failed: {error}");
+            Ok(())
+        }
+        false => Err(CliError::post_build_hook_failed(hook, error).into()),
+    }
+}
+
+/// Builds the package at the given path as `leo build` does, for callers which don't parse command line arguments.
+///
+/// The compiler errors and warnings are emitted in the message format of the options.
+/// The package is built in a new compiler session, unless the caller already has one.
+pub fn build_package(package_path: &Path, options: BuildOptions) -> Result<BuildOutput> {
+    let build = Build::new(options);
+    let context = Context::new(Some(package_path.to_path_buf()))?;
+    create_session_if_not_set_then(|_| build.apply(context, ()))
+}
+
+impl Build {
+    /// Returns the command building a single package with the given options.
+    pub(crate) fn new(options: BuildOptions) -> Self {
+        Self { options, ..Default::default() }
+    }
+
+    /// Returns `true` if the build prints an output to stdout, in which case all logs are written to stderr.
+    pub(crate) fn writes_to_stdout(&self) -> bool {
+        matches!(self.options.print_ast, Some(Some(_))) || matches!(self.options.circuit_out, Some(Some(_)))
+    }
+
+    /// Builds the entry files in the `bins` section of the manifest, or only the one selected by `--bin`.
+    /// Each entry file is built as if it were given with `--entry`, so their artifacts are named after them.
+    fn build_bins(
+        &self,
+        context: Context,
+        handler: &Handler,
+        timeout: Option<&Timeout>,
+        bins: &[BinConfig],
+    ) -> Result<BuildOutput> {
+        let package_path = context.dir()?;
+        let selected = match &self.options.bin {
+            Some(name) => match bins.iter().find(|bin| &bin.name == name) {
+                Some(bin) => vec![bin],
+                None => {
+                    let names = bins.iter().map(|bin| format!("`{}`", bin.name)).collect::<Vec<_>>();
+                    return Err(CliError::unknown_bin(name, names.join(", ")).into());
+                }
+            },
+            None => bins.iter().collect(),
+        };
+
+        let mut outputs = IndexMap::new();
+        for bin in selected {
+            tracing::info!("Building the entry '{}' at '{}'", bin.name, bin.path.display());
+            let mut options = self.options.clone();
+            options.entry = Some(package_path.join(&bin.path));
+            options.bin = None;
+            let build = Build { options, manifest_path: None, since: None, ..*self };
+            outputs.insert(bin.name.clone(), build.build(context.clone(), handler, timeout)?);
+        }
+
+        // `bins` is only empty with `--bin`, which fails above, so there is a last output.
+        let mut output = outputs.values().last().cloned().ok_or_else(|| CliError::unknown_bin("", ""))?;
+        output.structs = outputs.values().flat_map(|output| output.structs.clone()).collect();
+        output.tests = outputs.values().flat_map(|output| output.tests.clone()).collect();
+        output.recompiled = outputs.values().any(|output| output.recompiled);
+        output.bins = outputs;
+        Ok(output)
+    }
+
+    /// Builds the package, emitting compiler errors and warnings through `handler`.
+    pub(crate) fn build(&self, context: Context, handler: &Handler, timeout: Option<&Timeout>) -> Result<BuildOutput> {
+        // Get the package path.
+        let package_path = context.dir()?;
+
+        // Read the Leo-specific configuration from the manifest, validating the program name before any other IO.
+        // Only a missing manifest is replaced by `--no-manifest`, so that a malformed one is still an error.
+        let synthetic_manifest = self.options.no_manifest && !package_path.join(MANIFEST_FILENAME).exists();
+        let config = match synthetic_manifest {
+            true => ManifestConfig::synthetic(&package_path)?,
+            false => ManifestConfig::read_from(&package_path)?,
+        };
+        config.validate()?;
+
+        // The `compiler` section of the manifest holds the defaults of the flags which are not given.
+        for key in config.compiler.unknown.keys() {
+            tracing::warn!("Ignoring the unknown key `{key}` in the `compiler` section of '{MANIFEST_FILENAME}'");
+        }
+        let options = self.options.with_manifest_defaults(&config.compiler);
+
+        // The entry files in the `bins` section are built one after the other, unless an entry file is given.
+        if options.entry.is_none() && !options.lib_only && (!config.bins.is_empty() || options.bin.is_some()) {
+            return self.build_bins(context, handler, timeout, &config.bins);
+        }
+
+        // The compiled programs are always for the curve of `CurrentNetwork`, so the curve only has to be supported.
+        let Curve::Bls12_377 = options.curve()?;
+
+        // A read-only package is built into its custom output directory, so that nothing is written to it.
+        let build_root = options.build_root(&package_path)?;
+        if build_root != package_path {
+            tracing::info!("The package directory is read-only, building into '{}'", build_root.display());
+        }
+
+        // Get the program id.
+        // A dry run opens the manifest directly, as `Context::open_manifest` mirrors it into the build directory.
+        // A synthetic manifest is only written to the build directory, where snarkVM reads it.
+        let program_id = &match (synthetic_manifest, self.dry_run) {
+            (true, _) => {
+                tracing::info!("Building without '{MANIFEST_FILENAME}', as the program '{}'", config.program);
+                let program_id = ProgramID::<CurrentNetwork>::from_str(&config.program)
+                    .map_err(PackageError::failed_to_open_manifest)?;
+                if !self.dry_run {
+                    // snarkVM doesn't overwrite a manifest, so the one of the previous build is removed first.
+                    let build_path = BuildDirectory::create(&build_root.join(BUILD_DIRECTORY_NAME))?;
+                    let build_manifest_path = build_path.join(MANIFEST_FILENAME);
+                    if build_manifest_path.exists() {
+                        std::fs::remove_file(&build_manifest_path).map_err(PackageError::failed_to_open_manifest)?;
+                    }
+                    Manifest::create(&build_path, &program_id).map_err(PackageError::failed_to_open_manifest)?;
+                }
+                program_id
+            }
+            (false, true) => *Manifest::<CurrentNetwork>::open(&package_path)
+                .map_err(PackageError::failed_to_open_manifest)?
+                .program_id(),
+            (false, false) => *context.open_manifest_into(&build_root)?.program_id(),
+        };
+
+        // Create the outputs directory, resolving a custom output directory against the cwd.
+        let outputs_directory = options.outputs_directory(&package_path)?;
+        if !self.dry_run {
+            OutputsDirectory::create_at(&outputs_directory)?;
+        }
+
+        // Open the build directory.
+        let build_directory = match self.dry_run {
+            true => build_root.join(BUILD_DIRECTORY_NAME),
+            false => BuildDirectory::open(&build_root)?,
+        };
+
+        // Initialize a node counter.
+        let node_builder = NodeBuilder::default();
+
+        // Compile the entry file if one is given, the library with `--lib-only`,
+        // or otherwise the only .leo file in the source directory.
+        let source_directory = options.source_directory(&package_path, &config.build)?;
+        let source_files = match (&options.entry, options.lib_only) {
+            (Some(entry), _) => vec![entry_file_path(entry, &source_directory)?],
+            (None, true) => vec![lib_file_path(&source_directory)?],
+            (None, false) => {
+                check_entry_files(&source_directory)?;
+                source_files(&package_path, &source_directory, options.list_ignored)?
+            }
+        };
+
+        // The transitions of the source files are read again to translate an input file in JSON or TOML.
+        let entry_files = source_files.clone();
+
+        // Store all struct declarations made in the source files.
+        let mut structs = IndexMap::new();
+
+        // Tracks whether a program has been recompiled, and the imports which have been. The programs compiled after
+        // an import which import it are recompiled as well, as they may depend on it, and the others are only
+        // recompiled if they changed themselves.
+        let mut recompiled = false;
+        let mut recompiled_imports = Vec::new();
+
+        // Time each phase of the build.
+        let mut timings = BuildTimings::default();
+        let start = Instant::now();
+
+        // Fetch paths to all .leo files in the imports directory, and to the imports mapped in the manifest.
+        let import_paths = resolve_import_paths(&package_path, &config.imports)?;
+        let import_files = import_files(&package_path, &source_directory, &import_paths, options.list_ignored)?;
+        // Every import is read from its file in the package, rather than from the `imports/` directory of the cwd.
+        let import_paths = import_files
+            .iter()
+            .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path.clone())))
+            .collect::<BTreeMap<_, _>>();
+
+        // The artifacts in the outputs directory are named after the package, its imports, or its entry files.
+        let artifact_names = import_files
+            .iter()
+            .chain(SourceDirectory::files_in(&source_directory)?.iter())
+            .filter_map(|path| path.file_stem().and_then(|name| name.to_str()).map(str::to_string))
+            .chain([program_id.name().to_string()])
+            .chain(
+                options
+                    .checksum_name
+                    .iter()
+                    .filter_map(|name| name.strip_suffix(CHECKSUM_FILE_EXTENSION))
+                    .map(str::to_string),
+            )
+            .collect::<Vec<_>>();
+
+        // Load the input file at `package_name.in`, or at `package_name.NAME.in` if an input name is given.
+        let package_name = program_id.name().to_string();
+        let input_file = match &options.input_name {
+            Some(input_name) => InputFile::named(&package_name, input_name),
+            None => InputFile::new(&package_name),
+        };
+        // Reads the input file, with the files it includes, without parsing it. A library is built for publishing, so
+        // it isn't run with any inputs.
+        let read_input = || -> Result<Option<(PathBuf, String)>> {
+            // The input file of a custom input directory must exist, as the directory is only given to read it.
+            let input_file_path = match &options.input_dir {
+                Some(input_dir) => {
+                    if !input_dir.is_dir() {
+                        return Err(CliError::input_directory_not_found(input_dir.display()).into());
+                    }
+                    let input_file_name = format!("{}{}", input_file.package_name, options.input_format.extension());
+                    let input_file_path = input_dir.join(&input_file_name);
+                    if !input_file_path.exists() {
+                        return Err(CliError::input_file_not_in_directory(input_file_name, input_dir.display()).into());
+                    }
+                    input_file_path
+                }
+                None => match options.input_format {
+                    InputFormat::Leo => input_file.setup_file_path(&package_path).into_owned(),
+                    format => package_path.join(INPUTS_DIRECTORY_NAME).join(format!(
+                        "{}{}",
+                        input_file.package_name,
+                        format.extension()
+                    )),
+                },
+            };
+            if options.input_name.is_some() && !input_file_path.exists() {
+                return Err(CliError::named_input_file_not_found(input_file_path.display()).into());
+            }
+            if !input_file_path.exists() || options.lib_only {
+                return Ok(None);
+            }
+
+            let (input, _) = input_file.read_from(&input_file_path)?;
+            let input = match options.input_format {
+                InputFormat::Leo => resolve_input_includes(&input, &input_file_path, &mut Vec::new())?,
+                // An input file in JSON or TOML is translated once the transitions of the program are parsed.
+                _ => input,
+            };
+            Ok(Some((input_file_path, input)))
+        };
+
+        // The imports of each compiled Leo file, by file name, and whether it is in `src/`.
+        let mut depgraph = Vec::new();
+
+        // The programs are compiled on this thread, which runs the session of the compiler, while the input file is read
+        // on another. The errors of the compilation are returned before those of the input file, as if they were
+        // sequential.
+        let mut tests = Vec::new();
+        let input = std::thread::scope(|scope| -> Result<_> {
+            let span = tracing::Span::current();
+            let reader = scope.spawn(move || span.in_scope(read_input));
+
+            if !import_files.is_empty() {
+                // Create Aleo build/imports/ directory.
+                let build_imports_directory = match self.dry_run {
+                    true => build_directory.join(IMPORTS_DIRECTORY_NAME),
+                    false => ImportsDirectory::create(&build_directory)?,
+                };
+
+                // Compile all .leo files into .aleo files.
+                for file_path in import_files.into_iter() {
+                    if let Some(timeout) = timeout {
+                        timeout.set_status(format!("compiling the import '{}'", file_path.display()))?;
+                    }
+                    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    let program_name = program_name(&file_name, program_id, true)?;
+                    let force = imports_any_program(&file_path, &recompiled_imports);
+                    let compiled = compile_leo_file(
+                        file_path,
+                        &package_path,
+                        program_id,
+                        &outputs_directory,
+                        &build_imports_directory,
+                        handler,
+                        options.clone(),
+                        &import_paths,
+                        config.build.checksum_algorithm,
+                        force,
+                        true,
+                        self.dry_run,
+                    )?;
+                    structs.extend(compiled.structs);
+                    recompiled |= compiled.compiled;
+                    if compiled.compiled {
+                        recompiled_imports.push(program_name);
+                    }
+                    timings.lines += compiled.lines;
+                    timings.program_size += compiled.program_size;
+                    depgraph.push((file_name, compiled.imports, false));
+                }
+            }
+
+            timings.imports = start.elapsed();
+            let start = Instant::now();
+
+            // Compile all .leo files into .aleo files.
+            for file_path in source_files.into_iter() {
+                if let Some(timeout) = timeout {
+                    timeout.set_status(format!("compiling '{}'", file_path.display()))?;
+                }
+                let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                let force = imports_any_program(&file_path, &recompiled_imports);
+                let compiled = compile_leo_file(
+                    file_path,
+                    &package_path,
+                    program_id,
+                    &outputs_directory,
+                    &build_directory,
+                    handler,
+                    options.clone(),
+                    &import_paths,
+                    config.build.checksum_algorithm,
+                    force,
+                    false,
+                    self.dry_run,
+                )?;
+                structs.extend(compiled.structs);
+                recompiled |= compiled.compiled;
+                tests.extend(compiled.tests);
+                timings.lines += compiled.lines;
+                timings.program_size += compiled.program_size;
+                depgraph.push((file_name, compiled.imports, true));
+            }
+
+            timings.sources = start.elapsed();
+
+            reader.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })?;
+
+        if options.emit_depgraph {
+            let depgraph_file = DepgraphFile::new(&program_id.name().to_string());
+            let graph = depgraph_dot(&program_id.name().to_string(), &depgraph);
+            match self.dry_run {
+                true => log_dry_run_write(&outputs_directory.join(depgraph_file.file_name()), graph.len()),
+                false => depgraph_file.write_to(&outputs_directory, graph)?,
+            }
+        }
+
+        // The throughput only covers the files compiled by this build, as the others were up to date.
+        if timings.lines > 0 {
+            log_throughput(timings.lines, timings.imports + timings.sources);
+        }
+
+        // Fail the build if warnings are denied and any were emitted.
+        if options.deny_warnings && handler.warning_count() > 0 {
+            return Err(CliError::warnings_denied(handler.warning_count()).into());
+        }
+        let start = Instant::now();
+
+        // Parse the input file, which was read while the programs were compiled.
+        if let Some(timeout) = timeout {
+            timeout.set_status("parsing the input file")?;
+        }
+        let input_ast = match input {
+            Some((input_file_path, input)) => {
+                let input = match options.input_format {
+                    InputFormat::Leo => input,
+                    format => {
+                        let transitions = transitions(&entry_files)?;
+                        translate_structured_input(&input, format, &input_file_path, &transitions)?
+                    }
+                };
+                // Substitute the environment variables referenced as `${NAME}` before parsing the input file.
+                let (input, env_inputs) =
+                    substitute_env_vars(&input, &input_file_path, |name| std::env::var(name).ok())?;
+                for (register, variable) in env_inputs {
+                    tracing::info!("Read the input `{register}` from the environment variable `{variable}`");
+                }
+
+                // Load the input file into the source map.
+                let input_sf =
+                    with_session_globals(|s| s.source_map.new_source(&input, FileName::Real(input_file_path.clone())));
+
+                // An input file which fails to parse is ignored, as the program may be built without its inputs.
+                leo_parser::parse_input(handler, &node_builder, &input_sf.src, input_sf.start_pos)
+                    .map_err(|error| {
+                        tracing::warn!("Failed to parse the input file '{}': {error}", input_file_path.display())
+                    })
+                    .ok()
+            }
+            None => None,
+        };
+
+        // Check the input file against the inputs of the compiled functions, before snarkVM runs any of them.
+        if let (Some(input_ast), false) = (&input_ast, self.dry_run) {
+            validate_program_inputs(input_ast, &read_aleo_file(&build_directory.join(MAIN_ALEO_FILE_NAME))?)?;
+        }
+
+        timings.input = start.elapsed();
+        let start = Instant::now();
+
+        // `Package::open` checks that the build directory and that `main.aleo` and all imported files are well-formed.
+        // A dry run checks the compiled programs in memory instead.
+        if let Some(timeout) = timeout {
+            timeout.set_status("checking the compiled programs with snarkVM")?;
+        }
+        if !self.dry_run {
+            let package =
+                Package::<CurrentNetwork>::open(&build_directory).map_err(CliError::failed_to_execute_build)?;
+            if options.lib_only {
+                tracing::info!("✅ The library '{}' is ready to be published", program_id);
+            }
+            let only_function = options.only_function.as_deref();
+
+            // The circuits are only synthesized to check them against a constraint budget, which a library ignores.
+            if options.max_constraints.is_some() && !options.lib_only {
+                let mut process = package.get_process().map_err(CliError::failed_to_synthesize_keys)?;
+                let constraints = check_constraints(
+                    &mut process,
+                    package.program(),
+                    only_function,
+                    options.max_constraints,
+                    &mut options.rng(),
+                    timeout,
+                )?;
+                timings.constraints = Some(constraints);
+                if timings.lines > 0 {
+                    tracing::info!("{:.1} constraints per line", constraints as f64 / timings.lines as f64);
+                }
+            }
+
+            // A partial circuit is always written, as it is built to look at the circuit of the function.
+            if options.circuit_out.is_some() || options.only_function.is_some() {
+                let to_stdout = matches!(options.circuit_out, Some(Some(_)));
+                let process = package.get_process().map_err(CliError::failed_to_synthesize_keys)?;
+                let rng = &mut options.rng();
+                let circuit = serialize_circuit(&process, package.program(), only_function, rng, timeout)?;
+                let json = circuit.to_json_string()?;
+                // A partial circuit is written to a file of its own, so that it never replaces that of the program.
+                let circuit_file = CircuitFile::new(&package_name)
+                    .with_only_function(only_function.map(str::to_string))
+                    .with_compression(options.compress)
+                    .with_content_addressing(options.content_addressed);
+                match to_stdout {
+                    true => println!("{json}"),
+                    false => circuit_file.write_to(&outputs_directory, json)?,
+                }
+
+                // The written circuit is read back, which also checks its checksum.
+                if !to_stdout && options.verifies_serialization() {
+                    let written = SerializedCircuit::from_json_string(&circuit_file.read_from(&outputs_directory)?)?;
+                    if written != circuit {
+                        let path = outputs_directory.join(circuit_file.file_name());
+                        return Err(CliError::circuit_file_round_trip_mismatch(path.display()).into());
+                    }
+                }
+            }
+
+            if options.emit_constraint_report {
+                let process = package.get_process().map_err(CliError::failed_to_synthesize_keys)?;
+                let rng = &mut options.rng();
+                let report = constraint_report(&process, package.program(), only_function, rng, timeout)?;
+                ConstraintReportFile::new(&program_id.name().to_string()).write_to(&outputs_directory, report)?;
+            }
+
+            // The witness is only synthesized on request, as it holds the values of the private inputs.
+            if options.emit_witness {
+                match &input_ast {
+                    Some(input_ast) => {
+                        tracing::warn!(
+                            "The witness file '{}' may contain private values, such as private inputs and the contents of records. Don't share it.",
+                            outputs_directory.join(WitnessFile::new(&package_name).file_name()).display()
+                        );
+                        let process = package.get_process().map_err(CliError::failed_to_synthesize_keys)?;
+                        let signer = witness_signer(&package_path, &mut options.rng())?;
+                        let (program, rng) = (package.program(), &mut options.rng());
+                        let witness = serialize_witness(
+                            &process,
+                            program,
+                            input_ast,
+                            &structs,
+                            only_function,
+                            signer,
+                            rng,
+                            timeout,
+                        )?;
+                        WitnessFile::new(&package_name).write_to(&outputs_directory, witness.to_json_string()?)?;
+                    }
+                    None => tracing::warn!("No witness is written, as there is no input file to synthesize it on"),
+                }
+            }
+        }
+        timings.package = start.elapsed();
+
+        // The analysis only reads the compiled program, so that it can't change what is written.
+        if options.analyze && !self.dry_run {
+            log_repeated_patterns(&read_aleo_file(&build_directory.join(MAIN_ALEO_FILE_NAME))?);
+        }
+        if options.report_memory {
+            timings.peak_memory = report_peak_memory();
+        }
+        tracing::debug!("Build timings: {:?}", timings);
+
+        // A custom output directory may be shared with other packages, so only the default one is checked.
+        if options.output_dir.is_none() {
+            let stale_artifacts = stale_artifacts(&outputs_directory, &artifact_names)?;
+            if !stale_artifacts.is_empty() {
+                let names =
+                    stale_artifacts.iter().filter_map(|path| path.file_name()).map(|name| name.to_string_lossy());
+                let names = names.map(|name| format!("'{name}'")).collect::<Vec<_>>().join(", ");
+                match options.prune && !self.dry_run {
+                    true => {
+                        for path in &stale_artifacts {
+                            std::fs::remove_file(path)
+                                .map_err(|e| PackageError::failed_to_remove_file(path.display(), e))?;
+                        }
+                        tracing::info!("🧹 Pruned the stale artifacts {names}");
+                    }
+                    false => tracing::warn!(
+                        "The outputs directory holds artifacts of other programs, e.g. of a renamed package: {names}. Remove them with `--prune`."
+                    ),
+                }
+            }
+        }
+
+        // The build manifest is written last, so that it lists the artifacts as they are on disk after the build.
+        if !self.dry_run {
+            let build_manifest_file = BuildManifestFile::new(&package_name);
+            let artifacts = build_artifacts(&build_directory, &outputs_directory, &package_name)?;
+            build_manifest_file.write_to(&outputs_directory.join(build_manifest_file.file_name()), artifacts)?;
+        }
+
+        // // Unset the Leo panic hook.
+        // let _ = std::panic::take_hook();
+        //
+        // // Change the cwd to the build directory to compile aleo files.
+        // std::env::set_current_dir(&build_directory)
+        //     .map_err(|err| PackageError::failed_to_set_cwd(build_directory.display(), err))?;
+        //
+        // // Call the `build` command.
+        // let mut args = vec![SNARKVM_COMMAND];
+        // if self.options.offline {
+        //     args.push("--offline");
+        // }
+        // let command = AleoBuild::try_parse_from(&args).map_err(CliError::failed_to_execute_aleo_build)?;
+        // let result = command.parse().map_err(CliError::failed_to_execute_aleo_build)?;
+        //
+        // // Log the result of the build
+        // tracing::info!("{}", result);
+
+        Ok(BuildOutput {
+            input_ast,
+            structs,
+            tests,
+            timings,
+            persisted: !self.dry_run,
+            recompiled,
+            bins: IndexMap::new(),
+        })
+    }
+}
+
+/// Returns the directory of the package of the given manifest, after checking that the manifest can be opened.
+fn manifest_directory(manifest_path: &Path) -> Result<PathBuf> {
+    if !manifest_path.is_file() {
+        return Err(CliError::invalid_manifest_path(manifest_path.display(), "it doesn't exist").into());
+    }
+    // snarkVM only opens the manifest of a directory, which is always named `program.json`.
+    let file_name = Manifest::<CurrentNetwork>::file_name();
+    if manifest_path.file_name().and_then(|name| name.to_str()) != Some(file_name) {
+        return Err(
+            CliError::invalid_manifest_path(manifest_path.display(), format!("it isn't named `{file_name}`")).into()
+        );
+    }
+    let directory = match manifest_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    Manifest::<CurrentNetwork>::open(&directory).map_err(PackageError::failed_to_open_manifest)?;
+    Ok(directory)
+}
+
+/// Recursively copies the directory at `from` to `to`.
+pub(crate) fn copy_directory(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to).map_err(CliError::cli_io_error)?;
+    for entry in std::fs::read_dir(from).map_err(CliError::cli_io_error)? {
+        let path = entry.map_err(CliError::cli_io_error)?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+        match path.is_dir() {
+            true => copy_directory(&path, &target)?,
+            false => std::fs::copy(&path, &target).map(|_| ()).map_err(CliError::cli_io_error)?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns the names of the programs imported by the Leo files in the source directory of the package.
+fn imported_program_names(package_path: &Path, source_directory: &Path) -> Result<Vec<String>> {
+    let leoignore = Leoignore::read_from(package_path)?;
+    let mut names = Vec::new();
+    for file_path in SourceDirectory::files_in(source_directory)? {
+        if leoignore.is_ignored(package_path, &file_path) {
+            continue;
+        }
+        let source = std::fs::read_to_string(&file_path).map_err(CliError::cli_io_error)?;
+        names.extend(imported_programs(&source));
+    }
+    Ok(names)
+}
+
+/// Returns the names of the programs imported by the given Leo source, from its `import` statements.
+pub(crate) fn imported_programs(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("import "))
+        .filter_map(|import| import.split('.').next().map(|name| name.trim().to_string()))
+        .collect()
+}
+
+/// Returns `true` if the Leo file at the given path imports any of the given programs.
+/// A file whose imports can't be read may import any of them, so it is assumed to.
+fn imports_any_program(file_path: &Path, programs: &[String]) -> bool {
+    if programs.is_empty() {
+        return false;
+    }
+    match std::fs::read_to_string(file_path) {
+        Ok(source) => imported_programs(&source).iter().any(|name| programs.contains(name)),
+        Err(_) => true,
+    }
+}
+
+/// Compiles a program read from stdin, which is named `<stdin>` in errors, as a program of a synthetic package named
+/// after the program. There are no inputs, and nothing is written unless `--output-dir` is given.
+fn build_stdin(
+    options: &BuildOptions,
+    handler: &Handler,
+    timeout: Option<&Timeout>,
+) -> Result<<Build as Command>::Output> {
+    let mut source = String::new();
+    std::io::stdin().read_to_string(&mut source).map_err(CliError::cli_io_error)?;
+    let file_name = FileName::Custom(STDIN_FILE_NAME.to_string());
+
+    // The program is parsed once to find its name, which the compiler checks against the name of the package.
+    let sf = with_session_globals(|s| s.source_map.new_source(&source, file_name.clone()));
+    let ast = leo_parser::parse_ast(handler, &NodeBuilder::default(), &sf.src, sf.start_pos)?;
+    let program_id = ast.as_repr().program_scopes.values().next().map(|scope| scope.program_id);
+    let program_name = program_id.map_or_else(|| "stdin".to_string(), |id| id.name.to_string());
+    let network = program_id.map_or_else(|| "aleo".to_string(), |id| id.network.to_string());
+
+    // Snapshots and the Aleo instructions are only written to a given output directory.
+    let outputs = match &options.output_dir {
+        Some(_) => {
+            let outputs = options.outputs_directory(Path::new("."))?;
+            OutputsDirectory::create_at(&outputs)?;
+            Some(outputs)
+        }
+        None => None,
+    };
+    let mut compiler_options = CompilerOptions::from(options.clone());
+    if outputs.is_none() {
+        compiler_options.output = OutputOptions::default();
+    }
+
+    let mut compiler = Compiler::new(
+        program_name.clone(),
+        network,
+        handler,
+        PathBuf::from(STDIN_FILE_NAME),
+        outputs.clone().unwrap_or_default(),
+        Some(compiler_options),
+    );
+    let start = Instant::now();
+    compiler.parse_program_from_string(&source, file_name)?;
+    let (symbol_table, struct_graph, call_graph) = compiler.compiler_stages()?;
+    let instructions = compiler.code_generation_pass(&symbol_table, &struct_graph, &call_graph)?;
+    let program = parse_aleo_program(&instructions)?;
+    let timings = BuildTimings {
+        sources: start.elapsed(),
+        lines: non_blank_lines(&source),
+        program_size: instructions.len(),
+        ..Default::default()
+    };
+    tracing::info!("✅ Compiled '{STDIN_FILE_NAME}' into Aleo instructions");
+    log_throughput(timings.lines, timings.sources);
+
+    if let Some(outputs) = &outputs {
+        let aleo_file_path = outputs.join(format!("{program_name}.{}", program.id().network()));
+        write_atomically(&aleo_file_path, instructions.as_bytes()).map_err(CliError::failed_to_load_instructions)?;
+        tracing::info!("✅ Wrote the Aleo instructions to '{}'", aleo_file_path.display());
+    }
+
+    let mut process = Process::<Testnet3>::load().map_err(CliError::failed_to_synthesize_keys)?;
+    process.add_program(&program).map_err(CliError::failed_to_synthesize_keys)?;
+    let only_function = options.only_function.as_deref();
+    let constraints =
+        check_constraints(&mut process, &program, only_function, options.max_constraints, &mut options.rng(), timeout)?;
+    tracing::info!("{:.1} constraints per line", constraints as f64 / timings.lines.max(1) as f64);
+    let peak_memory = options.report_memory.then(report_peak_memory).flatten();
+
+    Ok(BuildOutput {
+        input_ast: None,
+        structs: symbol_table.structs,
+        tests: Vec::new(),
+        timings: BuildTimings { constraints: Some(constraints), peak_memory, ..timings },
+        persisted: outputs.is_some(),
+        recompiled: true,
+        bins: IndexMap::new(),
+    })
+}
+
+/// The number of instruction sequences reported by `--analyze`.
+const REPEATED_PATTERNS_REPORTED: usize = 5;
+
+/// Logs the sequences of two to four instructions repeated most often in the closures and functions of the program.
+/// Registers are ignored when comparing instructions, so that a sequence repeated on different values is found.
+/// A repeated sequence is a candidate for a function of its own in the Leo source.
+fn log_repeated_patterns(program: &Program<Testnet3>) {
+    let bodies = program
+        .closures()
+        .values()
+        .map(|closure| closure.instructions().iter().map(ToString::to_string).collect::<Vec<_>>())
+        .chain(
+            program
+                .functions()
+                .values()
+                .map(|function| function.instructions().iter().map(ToString::to_string).collect::<Vec<_>>()),
+        )
+        .map(|instructions| instructions.iter().map(|instruction| without_registers(instruction)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut counts = IndexMap::<Vec<String>, usize>::new();
+    for body in &bodies {
+        for length in 2..=4 {
+            for window in body.windows(length) {
+                *counts.entry(window.to_vec()).or_default() += 1;
+            }
+        }
+    }
+
+    // Longer sequences save more when factored out, so they are ranked by the instructions they account for.
+    let mut patterns = counts.into_iter().filter(|(_, count)| *count > 1).collect::<Vec<_>>();
+    patterns.sort_by_key(|(pattern, count)| std::cmp::Reverse(pattern.len() * count));
+    match patterns.is_empty() {
+        true => tracing::info!("No repeated instruction sequences found"),
+        false => {
+            tracing::info!("Most repeated instruction sequences:");
+            for (pattern, count) in patterns.iter().take(REPEATED_PATTERNS_REPORTED) {
+                tracing::info!("  {count} times: `{}`", pattern.join(" "));
+            }
+        }
+    }
+}
+
+/// Returns the instruction with its registers, such as `r0` or `r1.owner`, replaced by `r`.
+fn without_registers(instruction: &str) -> String {
+    instruction
+        .split(' ')
+        .map(|token| match token.strip_prefix('r') {
+            Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => {
+                format!("r{}", rest.trim_start_matches(|c: char| c.is_ascii_digit()))
+            }
+            _ => token.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the inputs of the given function from the input file.
+/// Without an input file, the function is run without inputs, which is only an error if it has parameters.
+pub(crate) fn input_file_inputs(output: BuildOutput, function_name: &str, package_path: &Path) -> Result<Vec<String>> {
+    if let BuildOutput { input_ast: Some(input_ast), structs, .. } = output {
+        return Ok(input_ast.program_inputs(function_name, structs));
+    }
+
+    // An unknown function is left to snarkVM to report.
+    let program = read_aleo_file(&package_path.join(BUILD_DIRECTORY_NAME).join(MAIN_ALEO_FILE_NAME))?;
+    let parameters = Identifier::<Testnet3>::from_str(function_name)
+        .ok()
+        .and_then(|name| program.get_function(&name).ok())
+        .map_or(0, |function| function.inputs().len());
+    match parameters {
+        0 => Ok(Vec::new()),
+        parameters => Err(CliError::missing_input_file(function_name, parameters).into()),
+    }
+}
+
+/// Lists the artifacts of the package on disk: the compiled programs and their checksums,
+/// and the keys and the proof if `leo setup` and `leo prove` wrote them.
+fn build_artifacts(build_directory: &Path, outputs: &Path, package_name: &str) -> Result<Vec<BuildArtifact>> {
+    let mut artifacts = Vec::new();
+    for (directory, kinds) in [
+        (
+            build_directory.to_path_buf(),
+            &[("aleo", "program"), ("avm", "avm"), ("prover", "proving_key"), ("verifier", "verifying_key")][..],
+        ),
+        (build_directory.join(IMPORTS_DIRECTORY_NAME), &[("aleo", "import")][..]),
+        (outputs.to_path_buf(), &[("sum", "checksum")][..]),
+    ] {
+        if !directory.is_dir() {
+            continue;
+        }
+        let entries = std::fs::read_dir(&directory)
+            .map_err(|e| PackageError::failed_to_read_directory(directory.display(), e))?
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(|e| PackageError::failed_to_read_directory(directory.display(), e))?;
+        let mut paths = entries.into_iter().map(|entry| entry.path()).filter(|path| path.is_file()).collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+            if let Some((_, kind)) = kinds.iter().find(|(candidate, _)| *candidate == extension) {
+                artifacts.push(BuildArtifact::from_file(kind, &path)?);
+            }
+        }
+    }
+
+    let proof_path = outputs.join(ProofFile::new(package_name).file_name());
+    if proof_path.is_file() {
+        artifacts.push(BuildArtifact::from_file("proof", &proof_path)?);
+    }
+
+    Ok(artifacts)
+}
+
+/// The results of compiling a Leo file.
+struct CompiledFile {
+    /// The struct declarations of the program.
+    structs: IndexMap<Symbol, Struct>,
+    /// Whether the program was compiled, rather than reused from the previous build.
+    compiled: bool,
+    /// The names of the transitions annotated with `@test`.
+    tests: Vec<String>,
+    /// The names of the programs imported by the program.
+    imports: Vec<String>,
+    /// The number of non-blank lines of the program if it was compiled, or zero if it was up to date.
+    lines: usize,
+    /// The size in bytes of the Aleo instructions of the program if it was compiled, or zero if it was up to date.
+    program_size: usize,
+}
+
+/// Compiles a Leo file in the `src/` directory.
+/// Compilation is skipped if the Leo file is unchanged since the previous build, unless `force` is set.
+/// A dry run writes no files, and only logs the files that would be written.
+#[allow(clippy::too_many_arguments)]
+fn compile_leo_file(
+    file_path: PathBuf,
+    package_path: &Path,
+    program_id: &ProgramID<Testnet3>,
+    outputs: &Path,
+    build: &Path,
+    handler: &Handler,
+    options: BuildOptions,
+    import_paths: &BTreeMap<String, PathBuf>,
+    checksum_algorithm: ChecksumAlgorithm,
+    force: bool,
+    is_import: bool,
+    dry_run: bool,
+) -> Result<CompiledFile> {
+    // Construct the Leo file name with extension `foo.leo`.
+    let file_name =
+        file_path.file_name().and_then(|name| name.to_str()).ok_or_else(PackageError::failed_to_get_file_name)?;
+
+    let program_name = program_name(file_name, program_id, is_import)?;
+
+    // Create the path to the Aleo file.
+    let mut aleo_file_path = build.to_path_buf();
+    aleo_file_path.push(match is_import {
+        true => format!("{program_name}.{}", program_id.network()),
+        false => format!("main.{}", program_id.network()),
+    });
+
+    // The checksum of the previous build is stored in the outputs directory.
+    let checksum_file = match (is_import, &options.checksum_name) {
+        (false, Some(checksum_name)) => ChecksumFile::new(&program_name).with_file_name(checksum_name),
+        _ => ChecksumFile::new(&program_name),
+    };
+    let checksum_path = outputs.join(checksum_file.file_name());
+    let ast_cache_file = AstCacheFile::new(&program_name).with_compression(options.compress);
+    let ast_cache_path = outputs.join(ast_cache_file.file_name());
+    let incremental = options.allows_incremental_build();
+    // The artifacts of an entry file are named after it, so that the artifacts of different entry files coexist.
+    let is_entry = options.entry.is_some() && !is_import;
+    let artifact_name = match is_entry {
+        true => file_name.strip_suffix(".leo").unwrap_or(file_name).to_string(),
+        false => program_name.clone(),
+    };
+    let emit_stats = options.emit_stats;
+    let emit_report = options.emit_report;
+    let emit_avm = options.emit_avm;
+    let warn_program_size = options.warn_program_size;
+    let verify_serialization = options.verifies_serialization();
+    let print_ast = options.print_ast.clone();
+    let allow_stale = options.allow_stale;
+    let skip_import = is_import && options.skip_imports;
+    let recover_caches = !options.no_cache_recovery;
+    let frozen = options.frozen;
+    let enable_dce = options.enable_dce;
+
+    // A dry run does not write any snapshots.
+    let mut compiler_options = CompilerOptions::from(options);
+    if dry_run {
+        compiler_options.output = OutputOptions::default();
+    }
+
+    // Create a new instance of the Leo compiler.
+    let mut compiler = Compiler::new(
+        program_name,
+        program_id.network().to_string(),
+        handler,
+        file_path.clone(),
+        outputs.to_path_buf(),
+        Some(compiler_options),
+    )
+    .with_import_paths(import_paths.clone());
+
+    // Compare the checksum of the Leo file against the checksum of the previous build.
+    let source = std::fs::read(&file_path).map_err(|e| CompilerError::file_read_error(&file_path, e))?;
+    // The checksum of an entry file also covers its name, as all entry files are compiled to the same Aleo file.
+    let checksum = match is_entry {
+        true => checksum_algorithm.checksum(&[file_name.as_bytes(), &source].concat()),
+        false => checksum_algorithm.checksum(&source),
+    };
+    // A program in `src/` is rebuilt when the input files change as well, so its checksum is stored
+    // along with a checksum combining it with the input files. Imports don't depend on the inputs.
+    let stored_checksum = match is_import {
+        true => checksum.clone(),
+        false => format!("{checksum}\n{}", inputs_checksum(package_path, &checksum, checksum_algorithm)?),
+    };
+    // Dead code elimination changes the compiled program, so a program built with it is marked as such.
+    let stored_checksum = match enable_dce {
+        true => format!("{stored_checksum}\n{DCE_CHECKSUM_LINE}"),
+        false => stored_checksum,
+    };
+    let (checksum_differs, previous_version) = match checksum_file.exists_at(&checksum_path) {
+        true => match checksum_file.read_with_compiler_version(&checksum_path) {
+            Ok((previous_checksum, version)) => (
+                previous_checksum != stored_checksum,
+                Some(version.unwrap_or_else(|| "an unknown version".to_string())),
+            ),
+            // A corrupt checksum file is removed, so that the program is recompiled.
+            Err(error) => {
+                remove_corrupt_cache(&checksum_path, error, recover_caches)?;
+                (true, None)
+            }
+        },
+        false => (true, None),
+    };
+    // A program built by a different version of Leo is only reused with `--allow-stale`.
+    let version_differs = previous_version.as_ref().is_some_and(|version| version != COMPILER_VERSION);
+    let is_stale = version_differs && !allow_stale;
+    if let (Some(version), true, false) = (&previous_version, version_differs, checksum_differs) {
+        match allow_stale {
+            true => tracing::warn!("Reusing '{file_name}', which was built by Leo {version}"),
+            false => {
+                tracing::info!("'{file_name}' was built by Leo {version}, recompiling with Leo {COMPILER_VERSION}")
+            }
+        }
+    }
+
+    // With `--skip-imports`, an import is reused whether or not it has changed, as long as it is cached.
+    let skipped_ast = match skip_import && is_aleo_file_well_formed(&aleo_file_path) {
+        true => read_ast_cache(&ast_cache_file, &ast_cache_path, None, allow_stale, recover_caches)?,
+        false => None,
+    };
+    if skip_import && skipped_ast.is_none() {
+        tracing::warn!("'{file_name}' is not cached, so it is compiled despite `--skip-imports`");
+    }
+    let skipped = skipped_ast.is_some();
+
+    // Skip compilation if the Leo file is unchanged and the previously compiled Aleo file is well-formed.
+    let (structs, instructions) = if skipped
+        || (incremental && !force && !checksum_differs && !is_stale && is_aleo_file_well_formed(&aleo_file_path))
+    {
+        // The struct declarations are still needed to construct the program inputs.
+        // Imports are not reparsed if their AST is cached.
+        let cached_ast = match skipped_ast {
+            Some(ast) => Some(ast),
+            None => read_ast_cache(&ast_cache_file, &ast_cache_path, Some(&checksum), allow_stale, recover_caches)?,
+        }
+        .filter(|_| is_import);
+        match cached_ast {
+            Some(ast) => compiler.ast = ast,
+            None => {
+                compiler.parse_program()?;
+                compiler.unused_import_pass();
+                if is_import && !dry_run {
+                    write_ast_cache(&ast_cache_file, &ast_cache_path, &checksum, &compiler.ast)?;
+                }
+            }
+        }
+        if let Some(path) = &print_ast {
+            write_parsed_ast(&compiler.ast, path.is_some(), outputs, &artifact_name, dry_run)?;
+        }
+        let symbol_table = compiler.symbol_table_pass()?;
+
+        match skipped {
+            true => tracing::info!("✅ Skipped compiling '{}'", file_name),
+            false => tracing::info!("✅ '{}' is up to date", file_name),
+        }
+        (symbol_table.structs, None)
+    } else {
+        // Compile the Leo program into Aleo instructions, caching the AST of imports after parsing.
+        compiler.parse_program()?;
+        if is_import && incremental && !dry_run {
+            write_ast_cache(&ast_cache_file, &ast_cache_path, &checksum, &compiler.ast)?;
+        }
+        if let Some(path) = &print_ast {
+            write_parsed_ast(&compiler.ast, path.is_some(), outputs, &artifact_name, dry_run)?;
+        }
+        let (symbol_table, struct_graph, call_graph) = compiler.compiler_stages()?;
+        let instructions = compiler.code_generation_pass(&symbol_table, &struct_graph, &call_graph)?;
+
+        // With `--frozen`, the program is compared against its previous build rather than written, so a build
+        // without a checksum, or with options disabling incremental builds, only fails if the program changed.
+        if frozen {
+            match std::fs::read_to_string(&aleo_file_path) {
+                Ok(previous) if previous == instructions => {}
+                Ok(_) => return Err(CliError::circuit_changed(file_name).into()),
+                Err(_) => return Err(CliError::frozen_without_build(file_name).into()),
+            }
+            tracing::info!("✅ '{}' matches its previous build", file_name);
+            (symbol_table.structs, None)
+        } else {
+            if dry_run {
+                // Check that the instructions are a well-formed program, as `Package::open` is not run.
+                parse_aleo_program(&instructions)?;
+                log_dry_run_write(&aleo_file_path, instructions.len());
+                if incremental {
+                    log_dry_run_write(
+                        &checksum_path,
+                        stored_checksum.len() + 1 + COMPILER_VERSION_PREFIX.len() + COMPILER_VERSION.len(),
+                    );
+                }
+            } else {
+                // Write the instructions, atomically so an interrupted build cannot leave a truncated file.
+                write_atomically(&aleo_file_path, instructions.as_bytes())
+                    .map_err(CliError::failed_to_load_instructions)?;
+
+                // Store the checksum so that the next build can be skipped if the Leo file is unchanged.
+                // Builds with options affecting the output discard it, forcing the next build to recompile.
+                match incremental {
+                    true => {
+                        checksum_file.write_with_compiler_version(&checksum_path, &stored_checksum, COMPILER_VERSION)?
+                    }
+                    false => {
+                        checksum_file.remove(&checksum_path)?;
+                    }
+                }
+            }
+
+            tracing::info!("✅ Compiled '{}' into Aleo instructions", file_name);
+            if let Some(threshold) = warn_program_size.filter(|&threshold| instructions.len() as u64 > threshold) {
+                tracing::warn!(
+                    "'{file_name}' compiled to {} bytes of Aleo instructions, more than the threshold of {threshold} bytes",
+                    instructions.len()
+                );
+            }
+            (symbol_table.structs, Some(instructions))
+        }
+    };
+    let compiled = instructions.is_some();
+    let lines = match compiled {
+        true => non_blank_lines(&String::from_utf8_lossy(&source)),
+        false => 0,
+    };
+    let program_size = instructions.as_ref().map_or(0, String::len);
+
+    // The transitions run by `leo test` are annotated with `@test`.
+    let tests = compiler
+        .ast
+        .as_repr()
+        .program_scopes
+        .values()
+        .flat_map(|scope| scope.functions.iter())
+        .filter(|(_, function)| function.annotations.iter().any(|annotation| annotation.identifier.name == sym::test))
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    if emit_stats || emit_report || emit_avm {
+        // The compiled program is only read from the Aleo file if it was not compiled by this build.
+        let program = match &instructions {
+            Some(instructions) => parse_aleo_program(instructions)?,
+            None => read_aleo_file(&aleo_file_path)?,
+        };
+
+        // Write the statistics of the compiled program.
+        if emit_stats {
+            let stats_file = StatsFile::new(&artifact_name);
+            let stats_path = outputs.join(stats_file.file_name());
+            let stats = program_stats(&program);
+            match dry_run {
+                true => log_dry_run_write(&stats_path, stats.len()),
+                false => stats_file.write_to(&stats_path, stats)?,
+            }
+        }
+
+        // Write the report of the compiled program.
+        if emit_report {
+            let report_file = ReportFile::new(&artifact_name);
+            let report_path = outputs.join(report_file.file_name());
+            let report = program_report(&program);
+            match dry_run {
+                true => log_dry_run_write(&report_path, report.len()),
+                false => report_file.write_to(&report_path, report)?,
+            }
+        }
+
+        // Write the compiled program in the binary AVM format.
+        if emit_avm {
+            write_avm_file(program, build, !is_import, verify_serialization, dry_run)?;
+        }
+    }
+
+    // The imports are resolved by the parser, and are kept by all later passes.
+    let imports = compiler.ast.as_repr().imports.keys().map(|name| name.to_string()).collect();
+
+    Ok(CompiledFile { structs, compiled, tests, imports, lines, program_size })
+}
+
+/// Writes the parsed AST of a program, with spans, to `{outputs}/{name}.ast.json`, or prints it to stdout.
+fn write_parsed_ast(ast: &Ast, to_stdout: bool, outputs: &Path, name: &str, dry_run: bool) -> Result<()> {
+    let json = ast.to_json_string()?;
+    if to_stdout {
+        println!("{json}");
+        return Ok(());
+    }
+    let path = outputs.join(format!("{name}.ast.json"));
+    match dry_run {
+        true => log_dry_run_write(&path, json.len()),
+        false => write_atomically(&path, json.as_bytes()).map_err(CliError::cli_io_error)?,
+    }
+    Ok(())
+}
+
+/// Returns the checksum and circuit files in the outputs directory which are not named after any of the given programs.
+/// The content-addressed circuits are in a subdirectory, so they are never returned, only the pointers to them.
+fn stale_artifacts(outputs: &Path, artifact_names: &[String]) -> Result<Vec<PathBuf>> {
+    let mut stale_artifacts = Vec::new();
+    if !outputs.exists() {
+        return Ok(stale_artifacts);
+    }
+    let entries =
+        std::fs::read_dir(outputs).map_err(|e| PackageError::failed_to_read_directory(outputs.display(), e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| PackageError::failed_to_read_directory(outputs.display(), e))?.path();
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        // Program names don't contain dots, so a circuit file is the only `.json` file whose name has no other dot.
+        let name = [
+            CHECKSUM_FILE_EXTENSION,
+            CIRCUIT_FILE_EXTENSION,
+            &format!("{CIRCUIT_FILE_EXTENSION}{GZIP_FILE_EXTENSION}"),
+        ]
+        .iter()
+        .find_map(|extension| file_name.strip_suffix(extension))
+        .filter(|name| !name.contains('.'));
+        if let Some(name) = name {
+            if path.is_file() && !artifact_names.iter().any(|artifact_name| artifact_name == name) {
+                stale_artifacts.push(path);
+            }
+        }
+    }
+    stale_artifacts.sort();
+    Ok(stale_artifacts)
+}
+
+/// Returns the number of non-blank lines of the given source.
+fn non_blank_lines(source: &str) -> usize {
+    source.lines().filter(|line| !line.trim().is_empty()).count()
+}
+
+/// Logs the number of lines compiled in the given time.
+fn log_throughput(lines: usize, elapsed: Duration) {
+    tracing::info!(
+        "Compiled {lines} lines in {} ms ({:.0} lines/s)",
+        elapsed.as_millis(),
+        lines as f64 / elapsed.as_secs_f64()
+    );
+}
+
+/// Logs the peak resident set size of the process, and returns it in bytes if the platform reports it.
+/// The peak is read at the end of the build, so it covers the whole process up to that point.
+fn report_peak_memory() -> Option<u64> {
+    let peak_memory = peak_memory();
+    match peak_memory {
+        Some(bytes) => tracing::info!("Peak memory usage: {:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        None => tracing::info!("Peak memory usage is not reported on this platform"),
+    }
+    peak_memory
+}
+
+/// Returns the peak resident set size of the process in bytes, which Linux reports as `VmHWM` in kB.
+#[cfg(target_os = "linux")]
+fn peak_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    let kilobytes = line.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Returns the peak resident set size of the process, which is only read on Linux.
+#[cfg(not(target_os = "linux"))]
+fn peak_memory() -> Option<u64> {
+    None
+}
+
+/// Returns the Graphviz graph of the imports between the given Leo files, with the files in `src/` highlighted.
+fn depgraph_dot(package_name: &str, files: &[(String, Vec<String>, bool)]) -> String {
+    let mut graph = format!("digraph \"{package_name}\" {{\n");
+    for (file_name, _, is_source) in files {
+        match is_source {
+            true => graph.push_str(&format!("    \"{file_name}\" [style=filled, fillcolor=lightblue];\n")),
+            false => graph.push_str(&format!("    \"{file_name}\";\n")),
+        }
+    }
+    for (file_name, imports, _) in files {
+        for import in imports {
+            graph.push_str(&format!("    \"{file_name}\" -> \"{import}{LEO_FILE_EXTENSION}\";\n"));
+        }
+    }
+    graph.push_str("}\n");
+    graph
+}
+
+/// Logs the size of a file that a dry run would have written.
+fn log_dry_run_write(path: &Path, size: usize) {
+    tracing::info!("Would write '{}' ({} bytes)", path.display(), size);
+}
+
+/// Returns the checksum of the program checksum combined with the input and state files of the package.
+fn inputs_checksum(package_path: &Path, program_checksum: &str, algorithm: ChecksumAlgorithm) -> Result<String> {
+    let mut files = Vec::new();
+    if package_path.join(INPUTS_DIRECTORY_NAME).exists() {
+        for path in InputsDirectory::files(package_path)? {
+            // The files are named relative to the package, so that moving the package doesn't trigger a rebuild.
+            let name = path.strip_prefix(package_path).unwrap_or(&path).to_string_lossy().to_string();
+            if name.ends_with(INPUT_FILE_EXTENSION) || name.ends_with(STATE_FILE_EXTENSION) {
+                let contents = std::fs::read(&path).map_err(|e| CompilerError::file_read_error(&path, e))?;
+                files.push((name, contents));
+            }
+        }
+    }
+    // The files are sorted, as the order in which they are listed depends on the file system.
+    files.sort();
+    Ok(algorithm.combined_checksum(program_checksum, &files))
+}
+
+/// The version of Leo recorded in the artifacts it builds.
+pub(crate) const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An AST cached in the outputs directory, which is valid for the Leo file with the given checksum.
+#[derive(Deserialize, Serialize)]
+struct AstCache {
+    format_version: u32,
+    compiler_version: String,
+    checksum: String,
+    ast: AstProgram,
+}
+
+/// The format version of an AST cache, which is read first so that a cache of another format isn't deserialized.
+#[derive(Deserialize)]
+struct AstCacheVersion {
+    format_version: u32,
+}
+
+/// Returns the cached AST of the Leo file with the given checksum, or with any checksum if none is given,
+/// if the cache is valid.
+/// ASTs of programs with imports are not cached, as their spans are needed to report unused imports.
+/// An AST cached by a different version of Leo is only used if stale caches are allowed.
+/// A cache which can't be read is corrupt, and is removed if `recover` is set, or is otherwise an error.
+fn read_ast_cache(
+    file: &AstCacheFile,
+    path: &Path,
+    checksum: Option<&str>,
+    allow_stale: bool,
+    recover: bool,
+) -> Result<Option<Ast>> {
+    if !file.exists_at(path) {
+        return Ok(None);
+    }
+    let corrupt = || PackageError::failed_to_read_ast_cache_file(path).into();
+    let contents = file.read_from(path).ok();
+    let version = contents.as_deref().and_then(|contents| serde_json::from_str::<AstCacheVersion>(contents).ok());
+    let (contents, version) = match (contents, version) {
+        (Some(contents), Some(version)) => (contents, version),
+        _ => return remove_corrupt_cache(path, corrupt(), recover).map(|_| None),
+    };
+    // A cache of another format version is outdated rather than corrupt, and is overwritten by the next build.
+    if version.format_version != AST_CACHE_FORMAT_VERSION {
+        return Ok(None);
+    }
+    // The AST is deserialized from the text of the cache, as not all of its types can be read from a JSON value.
+    let cache: AstCache = match serde_json::from_str(&contents) {
+        Ok(cache) => cache,
+        Err(_) => return remove_corrupt_cache(path, corrupt(), recover).map(|_| None),
+    };
+    let is_valid = cache.format_version == AST_CACHE_FORMAT_VERSION
+        && (allow_stale || cache.compiler_version == COMPILER_VERSION)
+        && (checksum.is_none() || checksum == Some(cache.checksum.as_str()))
+        && cache.ast.imports.is_empty();
+    if is_valid && cache.compiler_version != COMPILER_VERSION {
+        tracing::warn!("Reusing the AST cached by Leo {} in '{}'", cache.compiler_version, path.display());
+    }
+    Ok(is_valid.then(|| Ast::new(cache.ast)))
+}
+
+/// Removes a cache file which can't be read, so that the program is recompiled, unless recovery is disabled with
+/// `--no-cache-recovery`, in which case the error reading it is returned.
+fn remove_corrupt_cache(path: &Path, error: LeoError, recover: bool) -> Result<()> {
+    if !recover {
+        return Err(error);
+    }
+    tracing::warn!("Recompiling after removing a corrupt cache: {error}");
+    std::fs::remove_file(path).map_err(|e| PackageError::failed_to_remove_file(path.display(), e))?;
+    Ok(())
+}
+
+/// Caches the AST of the Leo file with the given checksum.
+fn write_ast_cache(file: &AstCacheFile, path: &Path, checksum: &str, ast: &Ast) -> Result<()> {
+    let cache = AstCache {
+        format_version: AST_CACHE_FORMAT_VERSION,
+        compiler_version: COMPILER_VERSION.to_string(),
+        checksum: checksum.to_string(),
+        ast: ast.as_repr().clone(),
+    };
+    file.write_to(path, serde_json::to_string(&cache).map_err(CliError::failed_to_write_file)?)
+}
+
+/// Returns the canonical paths of the imports mapped in the manifest, by import name.
+/// The mapped files must be named after their import and be in one of the import roots.
+pub(crate) fn resolve_import_paths(package_path: &Path, imports: &ImportsConfig) -> Result<BTreeMap<String, PathBuf>> {
+    let roots = match imports.roots.is_empty() {
+        true => vec![package_path.to_path_buf()],
+        false => imports.roots.iter().map(|root| package_path.join(root)).collect(),
+    };
+    // Canonical paths resolve `..` and symbolic links, so that a mapped file can't escape the roots.
+    // Roots which don't exist don't contain any files.
+    let canonical_roots = roots.iter().filter_map(|root| root.canonicalize().ok()).collect::<Vec<_>>();
+
+    let mut import_paths = BTreeMap::new();
+    for (name, path) in &imports.paths {
+        let error = |reason: String| CliError::invalid_import_path(name, path.display(), reason);
+
+        let canonical_path =
+            package_path.join(path).canonicalize().map_err(|_| error("it does not exist".to_string()))?;
+        if !canonical_roots.iter().any(|root| canonical_path.starts_with(root)) {
+            let roots = roots.iter().map(|root| format!("'{}'", root.display())).collect::<Vec<_>>().join(", ");
+            return Err(error(format!("it is not in the import roots {roots}")).into());
+        }
+        if canonical_path.file_name().and_then(|file_name| file_name.to_str()) != Some(&format!("{name}.leo")) {
+            return Err(error(format!("it is not named `{name}.leo`")).into());
+        }
+
+        import_paths.insert(name.clone(), canonical_path);
+    }
+    Ok(import_paths)
+}
+
+/// Returns the paths to the .leo files in the source directory which are not ignored by `.leoignore`,
+/// logging the ignored files if `list_ignored` is set.
+pub(crate) fn source_files(package_path: &Path, source_directory: &Path, list_ignored: bool) -> Result<Vec<PathBuf>> {
+    let leoignore = Leoignore::read_from(package_path)?;
+    let (ignored, source_files): (Vec<_>, Vec<_>) = SourceDirectory::files_in(source_directory)?
+        .into_iter()
+        .partition(|path| leoignore.is_ignored(package_path, path));
+    if list_ignored {
+        for path in ignored {
+            log_ignored_file(package_path, &path);
+        }
+    }
+
+    // Check the source files.
+    SourceDirectory::check_files(&source_files)?;
+    Ok(source_files)
+}
+
+/// Returns the paths to all .leo files in the imports directory, and to the imports mapped to other paths.
+/// A mapped import takes precedence over the file with its name in the imports directory.
+/// Files ignored by `.leoignore` are skipped, unless they are imported, in which case the import is an error.
+pub(crate) fn import_files(
+    package_path: &Path,
+    source_directory: &Path,
+    import_paths: &BTreeMap<String, PathBuf>,
+    list_ignored: bool,
+) -> Result<Vec<PathBuf>> {
+    let leoignore = Leoignore::read_from(package_path)?;
+    let mut import_files = match ImportsDirectory::is_empty(package_path)? {
+        true => Vec::new(),
+        false => ImportsDirectory::files(package_path)?,
+    };
+    import_files.retain(|path| {
+        let name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+        !import_paths.contains_key(name)
+    });
+
+    // The compiler would only report an ignored import as a missing program.
+    let (ignored, mut import_files): (Vec<_>, Vec<_>) =
+        import_files.into_iter().partition(|path| leoignore.is_ignored(package_path, path));
+    if !ignored.is_empty() {
+        let imported_names = imported_program_names(package_path, source_directory)?;
+        for path in ignored {
+            let name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+            if imported_names.iter().any(|imported_name| imported_name == name) {
+                return Err(CliError::ignored_import(name, path.display()).into());
+            }
+            if list_ignored {
+                log_ignored_file(package_path, &path);
+            }
+        }
+    }
+    for (name, path) in import_paths {
+        if leoignore.is_ignored(package_path, path) {
+            return Err(CliError::ignored_import(name, path.display()).into());
+        }
+    }
+
+    import_files.extend(import_paths.values().cloned());
+    Ok(import_files)
+}
+
+/// Logs a file excluded from compilation by `.leoignore`, relative to the package.
+fn log_ignored_file(package_path: &Path, path: &Path) {
+    tracing::info!(
+        "Ignoring '{}', as it matches a pattern in '{LEOIGNORE_FILENAME}'",
+        path.strip_prefix(package_path).unwrap_or(path).display()
+    );
+}
+
+/// Returns the path to the given entry file, which must be a Leo file in the source directory of the package.
+/// Relative paths are resolved against the current working directory.
+pub(crate) fn entry_file_path(entry: &Path, source_directory: &Path) -> Result<PathBuf> {
+    let error = || CliError::entry_file_not_in_source_directory(entry.display());
+
+    let path = std::env::current_dir().map_err(CliError::cli_io_error)?.join(entry);
+    let path = path.canonicalize().map_err(|_| error())?;
+    let source_directory = source_directory.canonicalize().map_err(|_| error())?;
+
+    match path.is_file() && path.starts_with(source_directory) && path.to_string_lossy().ends_with(LEO_FILE_EXTENSION) {
+        true => Ok(path),
+        false => Err(error().into()),
+    }
+}
+
+/// Checks that the source directory has a `main.leo` or a `lib.leo` to build, as it may otherwise hold only other
+/// Leo files, or none at all. A library without a program is built, as it is published, but the build says so.
+fn check_entry_files(source_directory: &Path) -> Result<()> {
+    match (source_directory.join(MAIN_FILENAME).is_file(), source_directory.join(LIB_FILENAME).is_file()) {
+        (false, false) => Err(CliError::no_source_files(source_directory.display()).into()),
+        (false, true) => {
+            tracing::info!(
+                "The package has no `{MAIN_FILENAME}`, so its library `{LIB_FILENAME}` is built. Pass `--lib-only` to check it for publishing."
+            );
+            Ok(())
+        }
+        (true, _) => Ok(()),
+    }
+}
+
+/// Returns the path of `lib.leo` in the source directory, which `--lib-only` builds.
+fn lib_file_path(source_directory: &Path) -> Result<PathBuf> {
+    let path = source_directory.join(LIB_FILENAME);
+    match path.is_file() {
+        true => Ok(path),
+        false => Err(CliError::lib_file_not_found(path.display()).into()),
+    }
+}
+
+/// Returns the name of the program defined in the Leo file with the given name.
+pub(crate) fn program_name(file_name: &str, program_id: &ProgramID<Testnet3>, is_import: bool) -> Result<String> {
+    // If the program is an import, construct program name from file_path
+    // Otherwise, use the program_id found in `package.json`.
+    match is_import {
+        false => Ok(program_id.name().to_string()),
+        true => Ok(file_name.strip_suffix(".leo").ok_or_else(PackageError::failed_to_get_file_name)?.to_string()),
+    }
+}
+
+/// Returns `true` if the Aleo file at the given path exists and contains a well-formed program.
+fn is_aleo_file_well_formed(aleo_file_path: &Path) -> bool {
+    std::fs::read_to_string(aleo_file_path)
+        .map(|instructions| Program::<Testnet3>::from_str(&instructions).is_ok())
+        .unwrap_or(false)
+}
+
+/// Returns the statistics of the Aleo program at the given path as a JSON string.
+/// Constant and public inputs are counted as public, as their values are visible to the verifier.
+fn program_stats(program: &Program<Testnet3>) -> String {
+    let functions = program.functions().values();
+    let closures = program.closures().values();
+    let is_public =
+        |value_type: &ValueType<Testnet3>| matches!(value_type, ValueType::Constant(_) | ValueType::Public(_));
+    let (public_inputs, private_inputs): (Vec<_>, Vec<_>) = functions
+        .clone()
+        .flat_map(|function| function.inputs().iter())
+        .partition(|input| is_public(input.value_type()));
+
+    let stats = serde_json::json!({
+        "functions": program.functions().len(),
+        "closures": program.closures().len(),
+        "mappings": program.mappings().len(),
+        "inputs": functions.clone().map(|function| function.inputs().len()).sum::<usize>(),
+        "public_inputs": public_inputs.len(),
+        "private_inputs": private_inputs.len(),
+        "outputs": functions.clone().map(|function| function.outputs().len()).sum::<usize>(),
+        "instructions": functions.clone().map(|function| function.instructions().len()).sum::<usize>()
+            + closures.map(|closure| closure.instructions().len()).sum::<usize>(),
+        "finalize_commands": functions
+            .filter_map(|function| function.finalize_logic())
+            .map(|finalize| finalize.commands().len())
+            .sum::<usize>(),
+    });
+
+    stats.to_string()
+}
+
+/// Returns an error naming the first input in the input file that is missing, extra,
+/// or of another type than the corresponding input of the compiled function.
+/// Sections that are not named after a function of the program are not checked.
+fn validate_program_inputs(input_ast: &InputAst, program: &Program<Testnet3>) -> Result<()> {
+    for section in &input_ast.sections {
+        let Some(function) =
+            program.functions().values().find(|function| function.name().to_string() == section.name())
+        else {
+            continue;
+        };
+        let expected_inputs = function.inputs();
+
+        for (index, definition) in section.definitions.iter().enumerate() {
+            let Some(expected) = expected_inputs.get_index(index) else {
+                return Err(CliError::extra_program_input(
+                    definition.name,
+                    function.name(),
+                    expected_inputs.len(),
+                    definition.span,
+                )
+                .into());
+            };
+
+            // The modes in input files are not checked, as the inputs are passed to the function as plain values.
+            let expected_type = match expected.value_type() {
+                ValueType::Constant(type_) | ValueType::Public(type_) | ValueType::Private(type_) => type_.to_string(),
+                ValueType::Record(identifier) => identifier.to_string(),
+                ValueType::ExternalRecord(locator) => locator.resource().to_string(),
+                ValueType::Future(locator) => locator.to_string(),
+            };
+            // Array lengths are written as `u32` literals in Aleo instructions.
+            let expected_type = expected_type.replace("u32]", "]");
+            let received_type = definition.type_.to_string();
+            if !expected_type.eq_ignore_ascii_case(&received_type) {
+                return Err(CliError::mismatched_program_input(
+                    definition.name,
+                    expected.register(),
+                    expected_type,
+                    received_type,
+                    definition.span,
+                )
+                .into());
+            }
+        }
+
+        if let Some(missing) = expected_inputs.get_index(section.definitions.len()) {
+            return Err(CliError::missing_program_input(
+                function.name(),
+                missing.register(),
+                missing.value_type(),
+                section.span,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Returns a human-readable report of the size of each function and closure of the Aleo program at the given path.
+fn program_report(program: &Program<Testnet3>) -> String {
+    let mut report = format!("Program: {}\n\n", program.id());
+    for function in program.functions().values() {
+        report += &format!(
+            "function {}: {} inputs, {} outputs, {} instructions",
+            function.name(),
+            function.inputs().len(),
+            function.outputs().len(),
+            function.instructions().len()
+        );
+        if let Some(finalize) = function.finalize_logic() {
+            report += &format!(", {} finalize commands", finalize.commands().len());
+        }
+        report += "\n";
+    }
+    for closure in program.closures().values() {
+        report += &format!(
+            "closure {}: {} inputs, {} outputs, {} instructions\n",
+            closure.name(),
+            closure.inputs().len(),
+            closure.outputs().len(),
+            closure.instructions().len()
+        );
+    }
+
+    let instructions = program.functions().values().map(|function| function.instructions().len()).sum::<usize>()
+        + program.closures().values().map(|closure| closure.instructions().len()).sum::<usize>();
+    report += &format!(
+        "\nTotal: {} functions, {} closures, {} mappings, {instructions} instructions\n",
+        program.functions().len(),
+        program.closures().len(),
+        program.mappings().len()
+    );
+
+    report
+}
+
+/// Writes the Aleo program at the given path in the binary AVM format into the given directory.
+/// If `verify` is set, the written file is decoded again to check that it round-trips to the same program.
+fn write_avm_file(
+    program: Program<Testnet3>,
+    directory: &Path,
+    is_main: bool,
+    verify: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let file_name = match is_main {
+        true => "main.avm".to_string(),
+        false => format!("{}.avm", program.id()),
+    };
+    let path = directory.join(file_name);
+    match verify {
+        true => tracing::info!("Verifying that '{}' decodes to the compiled program", path.display()),
+        false => tracing::info!("Writing '{}' without verifying it (--no-verify-serialization)", path.display()),
+    }
+
+    // A dry run encodes and decodes the program in memory.
+    if dry_run {
+        let bytes = program.to_bytes_le().map_err(CliError::failed_to_write_file)?;
+        if verify && Program::<Testnet3>::from_bytes_le(&bytes).ok().as_ref() != Some(&program) {
+            return Err(CliError::avm_file_round_trip_mismatch(path.display()).into());
+        }
+        log_dry_run_write(&path, bytes.len());
+        return Ok(());
+    }
+
+    if !verify {
+        let bytes = program.to_bytes_le().map_err(CliError::failed_to_write_file)?;
+        return std::fs::write(&path, bytes).map_err(|err| CliError::failed_to_write_file(err).into());
+    }
+
+    // `AVMFile::create` decodes the file after writing it.
+    let avm_file = AVMFile::create(directory, program.clone(), is_main).map_err(CliError::failed_to_write_file)?;
+    if avm_file.program() != &program {
+        return Err(CliError::avm_file_round_trip_mismatch(path.display()).into());
+    }
+
+    Ok(())
+}
+
+/// Reads the Aleo program at the given path.
+fn read_aleo_file(aleo_file_path: &Path) -> Result<Program<Testnet3>> {
+    let instructions = std::fs::read_to_string(aleo_file_path).map_err(CliError::failed_to_load_instructions)?;
+    parse_aleo_program(&instructions)
+}
+
+/// Parses the given Aleo instructions into a program.
+fn parse_aleo_program(instructions: &str) -> Result<Program<Testnet3>> {
+    Ok(Program::<Testnet3>::from_str(instructions).map_err(CliError::failed_to_load_instructions)?)
+}
@@ -0,0 +1,354 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Synthesizing the circuits of a compiled program, to count, report, and serialize their constraints.
+
+use super::*;
+
+use leo_package::outputs::{
+    SerializedFunctionCircuit,
+    SerializedFunctionWitness,
+    SerializedLinearCombination,
+    SerializedVariable,
+    SerializedWitness,
+    WitnessRegister,
+};
+
+use snarkvm::{
+    circuit::{
+        AleoV0,
+        Assignment,
+        environment::{AssignmentLC, AssignmentVariable},
+    },
+    prelude::{Address, Assignments, CallMetrics, CallStack, Environment, PrivateKey, Request, StackExecute, Value},
+    synthesizer::program::StackProgram,
+};
+
+/// Synthesizes the circuits of the functions of the given program, or only of `only_function`, logging their number of
+/// constraints. Fails if a function has more constraints than the given budget, and otherwise returns the total number
+/// of constraints.
+pub(crate) fn check_constraints(
+    process: &mut Process<Testnet3>,
+    program: &Program<Testnet3>,
+    only_function: Option<&str>,
+    max_constraints: Option<u64>,
+    rng: &mut ChaChaRng,
+    timeout: Option<&Timeout>,
+) -> Result<u64> {
+    // The number of constraints of a function is that of the circuit of its synthesized key.
+    let mut constraints = 0;
+    for function_name in &synthesized_functions(program, only_function)? {
+        if let Some(timeout) = timeout {
+            timeout.set_status(format!("synthesizing the circuit of `{function_name}`"))?;
+        }
+        let (program_id, function) = (*program.id(), *function_name);
+        synthesize_within(timeout, process, rng, move |process, rng| {
+            process
+                .synthesize_key::<AleoV0, _>(&program_id, &function, rng)
+                .map_err(CliError::failed_to_synthesize_keys)?;
+            Ok(())
+        })?;
+        let verifying_key =
+            process.get_verifying_key(program.id(), function_name).map_err(CliError::failed_to_synthesize_keys)?;
+        let num_constraints = verifying_key.circuit_info.num_constraints as u64;
+        tracing::info!("`{function_name}`: {num_constraints} constraints");
+        if let Some(max_constraints) = max_constraints.filter(|max_constraints| num_constraints > *max_constraints) {
+            return Err(CliError::constraint_budget_exceeded(function_name, num_constraints, max_constraints).into());
+        }
+        if let Some(timeout) = timeout {
+            timeout.add_constraints(num_constraints);
+        }
+        constraints += num_constraints;
+    }
+    Ok(constraints)
+}
+
+/// Runs the given synthesis on a worker thread, with copies of the process and the rng, if the build has a time limit,
+/// so that the build times out even while snarkVM is synthesizing a circuit. The rng is advanced as if the synthesis
+/// ran on the current thread.
+fn synthesize_within<T: Send + 'static>(
+    timeout: Option<&Timeout>,
+    process: &Process<Testnet3>,
+    rng: &mut ChaChaRng,
+    synthesize: impl FnOnce(&Process<Testnet3>, &mut ChaChaRng) -> Result<T> + Send + 'static,
+) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return synthesize(process, rng);
+    };
+    let (process, mut worker_rng) = (process.clone(), rng.clone());
+    let (output, worker_rng) = timeout.run(move || (synthesize(&process, &mut worker_rng), worker_rng))?;
+    *rng = worker_rng;
+    output
+}
+
+/// A linear combination of the variables of a circuit, as enforced by one of its constraints.
+type ConstraintLC = AssignmentLC<<Testnet3 as Environment>::Field>;
+
+/// Synthesizes the circuit of the given function without keying it, returning it with the number of constraints
+/// spent on checking its request, running its instructions, and building its response.
+fn synthesize_assignment(
+    process: &Process<Testnet3>,
+    program_id: &ProgramID<Testnet3>,
+    function_name: &Identifier<Testnet3>,
+    rng: &mut ChaChaRng,
+) -> Result<(Assignment<<Testnet3 as Environment>::Field>, CallMetrics<Testnet3>)> {
+    let stack = process.get_stack(program_id).map_err(CliError::failed_to_synthesize_keys)?;
+    let input_types = stack.get_function(function_name).map_err(CliError::failed_to_synthesize_keys)?.input_types();
+
+    // The function is run on sampled inputs signed by a burner account, as `synthesize_key` does.
+    let burner_private_key = PrivateKey::new(rng).map_err(CliError::failed_to_synthesize_keys)?;
+    let burner_address = Address::try_from(&burner_private_key).map_err(CliError::failed_to_synthesize_keys)?;
+    let inputs = input_types
+        .iter()
+        .map(|input_type| match input_type {
+            ValueType::ExternalRecord(locator) => stack
+                .get_external_stack(locator.program_id())
+                .and_then(|stack| stack.sample_value(&burner_address, &ValueType::Record(*locator.resource()), rng)),
+            _ => stack.sample_value(&burner_address, input_type, rng),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CliError::failed_to_synthesize_keys)?;
+    synthesize_assignment_with_inputs(process, program_id, function_name, burner_private_key, inputs, rng)
+}
+
+/// Synthesizes the circuit of the given function without keying it, as `synthesize_assignment` does, on the given
+/// inputs signed by the given account, which must own the records among them.
+fn synthesize_assignment_with_inputs(
+    process: &Process<Testnet3>,
+    program_id: &ProgramID<Testnet3>,
+    function_name: &Identifier<Testnet3>,
+    private_key: PrivateKey<Testnet3>,
+    inputs: Vec<Value<Testnet3>>,
+    rng: &mut ChaChaRng,
+) -> Result<(Assignment<<Testnet3 as Environment>::Field>, CallMetrics<Testnet3>)> {
+    let stack = process.get_stack(program_id).map_err(CliError::failed_to_synthesize_keys)?;
+    let input_types = stack.get_function(function_name).map_err(CliError::failed_to_synthesize_keys)?.input_types();
+    let request = Request::sign(&private_key, *program_id, *function_name, inputs.into_iter(), &input_types, rng)
+        .map_err(CliError::failed_to_synthesize_keys)?;
+
+    // In the mode checking deployments, the circuits of the function and the functions it calls are
+    // returned rather than turned into keys.
+    let assignments = Assignments::<Testnet3>::default();
+    let call_stack = CallStack::CheckDeployment(vec![request], private_key, assignments.clone());
+    stack.execute_function::<AleoV0, _>(call_stack, None, rng).map_err(CliError::failed_to_synthesize_keys)?;
+
+    // The circuit of the function itself is the last one, after those of the functions it calls.
+    let assignments = std::mem::take(&mut *assignments.write());
+    assignments
+        .into_iter()
+        .last()
+        .ok_or_else(|| CliError::failed_to_synthesize_keys(format!("`{function_name}` produced no circuit")).into())
+}
+
+/// Returns the report of the number of constraints of each function of the given program, or only of `only_function`,
+/// split between checking its inputs, running its instructions, and building its outputs.
+/// The instructions of the Leo functions inlined into a transition are counted in its body, as the circuit doesn't
+/// record where they came from, and constraints outside of the three parts are counted as unknown.
+pub(super) fn constraint_report(
+    process: &Process<Testnet3>,
+    program: &Program<Testnet3>,
+    only_function: Option<&str>,
+    rng: &mut ChaChaRng,
+    timeout: Option<&Timeout>,
+) -> Result<String> {
+    let functions = synthesized_functions(program, only_function)?;
+    let mut report = match only_function {
+        Some(function) => format!("Program: {} (partial, only `{function}`)\n\n", program.id()),
+        None => format!("Program: {}\n\n", program.id()),
+    };
+    let mut total = 0;
+    for function_name in &functions {
+        if let Some(timeout) = timeout {
+            timeout.set_status(format!("synthesizing the circuit of `{function_name}` for the constraint report"))?;
+        }
+        let (program_id, function) = (*program.id(), *function_name);
+        let (assignment, metrics) = synthesize_within(timeout, process, rng, move |process, rng| {
+            synthesize_assignment(process, &program_id, &function, rng)
+        })?;
+        let constraints = assignment.num_constraints();
+        if let Some(timeout) = timeout {
+            timeout.add_constraints(constraints);
+        }
+        let attributed =
+            metrics.num_request_constraints + metrics.num_function_constraints + metrics.num_response_constraints;
+        report += &format!("function {function_name}: {constraints} constraints\n");
+        report += &format!("  inputs: {}\n", metrics.num_request_constraints);
+        report +=
+            &format!("  body: {} ({} instructions)\n", metrics.num_function_constraints, metrics.num_instructions);
+        report += &format!("  outputs: {}\n", metrics.num_response_constraints);
+        if constraints != attributed {
+            report += &format!("  unknown: {}\n", constraints.saturating_sub(attributed));
+        }
+        total += constraints;
+    }
+    report += &format!("\nTotal: {total} constraints in {} functions\n", functions.len());
+    Ok(report)
+}
+
+/// Synthesizes the circuits of the functions of the given program, or only of `only_function`, without keying them,
+/// for the circuit file.
+pub(super) fn serialize_circuit(
+    process: &Process<Testnet3>,
+    program: &Program<Testnet3>,
+    only_function: Option<&str>,
+    rng: &mut ChaChaRng,
+    timeout: Option<&Timeout>,
+) -> Result<SerializedCircuit> {
+    let mut functions = IndexMap::new();
+    for function_name in &synthesized_functions(program, only_function)? {
+        if let Some(timeout) = timeout {
+            timeout.set_status(format!("synthesizing the circuit of `{function_name}` for the circuit file"))?;
+        }
+        let (program_id, function) = (*program.id(), *function_name);
+        let (assignment, _) = synthesize_within(timeout, process, rng, move |process, rng| {
+            synthesize_assignment(process, &program_id, &function, rng)
+        })?;
+        if let Some(timeout) = timeout {
+            timeout.add_constraints(assignment.num_constraints());
+        }
+        functions.insert(function_name.to_string(), serialize_assignment(&assignment));
+    }
+    let mut circuit = SerializedCircuit {
+        program: program.id().to_string(),
+        only_function: only_function.map(str::to_string),
+        checksum: String::new(),
+        functions,
+    };
+    circuit.checksum = circuit.compute_checksum()?;
+    Ok(circuit)
+}
+
+/// Synthesizes the circuits of the functions of the given program with a section in the input file, or only of
+/// `only_function`, on the inputs of their sections, for the witness file.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn serialize_witness(
+    process: &Process<Testnet3>,
+    program: &Program<Testnet3>,
+    input_ast: &InputAst,
+    structs: &IndexMap<Symbol, Struct>,
+    only_function: Option<&str>,
+    signer: PrivateKey<Testnet3>,
+    rng: &mut ChaChaRng,
+    timeout: Option<&Timeout>,
+) -> Result<SerializedWitness> {
+    let mut functions = IndexMap::new();
+    for function_name in &synthesized_functions(program, only_function)? {
+        let name = function_name.to_string();
+        let Some(section) = input_ast.sections.iter().find(|section| section.name() == name) else {
+            tracing::info!("No witness is written for `{name}`, as the input file has no section for it");
+            continue;
+        };
+        if let Some(timeout) = timeout {
+            timeout.set_status(format!("synthesizing the witness of `{function_name}`"))?;
+        }
+
+        // The inputs are given in the order of the parameters, which are in the registers from `r0`.
+        let inputs = input_ast.program_inputs(&name, structs.clone());
+        let registers = section
+            .definitions
+            .iter()
+            .zip(&inputs)
+            .enumerate()
+            .map(|(index, (definition, value))| {
+                (format!("r{index}"), WitnessRegister { name: definition.name.to_string(), value: value.clone() })
+            })
+            .collect();
+        let inputs = inputs
+            .iter()
+            .map(|input| Value::<Testnet3>::from_str(input))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| CliError::failed_to_synthesize_witness(function_name, error))?;
+        let (program_id, function) = (*program.id(), *function_name);
+        let (assignment, _) = synthesize_within(timeout, process, rng, move |process, rng| {
+            synthesize_assignment_with_inputs(process, &program_id, &function, signer, inputs, rng)
+                .map_err(|error| CliError::failed_to_synthesize_witness(function, error).into())
+        })?;
+        if let Some(timeout) = timeout {
+            timeout.add_constraints(assignment.num_constraints());
+        }
+
+        let values = |variables: &Vec<(u64, <Testnet3 as Environment>::Field)>| {
+            variables.iter().map(|(_, value)| value.to_string()).collect()
+        };
+        functions.insert(name, SerializedFunctionWitness {
+            registers,
+            input_assignment: values(assignment.public_inputs()),
+            aux_assignment: values(assignment.private_inputs()),
+        });
+    }
+    Ok(SerializedWitness { program: program.id().to_string(), functions })
+}
+
+/// Returns the account signing the inputs of the witness: that of the `PRIVATE_KEY` in the `.env` file of the package,
+/// which owns the records of the input file, or a burner account if there is none.
+pub(super) fn witness_signer(package_path: &Path, rng: &mut ChaChaRng) -> Result<PrivateKey<Testnet3>> {
+    let private_key = dotenvy::from_path_iter(package_path.join(".env"))
+        .ok()
+        .and_then(|variables| variables.flatten().find(|(name, _)| name == "PRIVATE_KEY"))
+        .map(|(_, private_key)| private_key);
+    match private_key {
+        Some(private_key) => PrivateKey::from_str(&private_key),
+        None => PrivateKey::new(rng),
+    }
+    .map_err(|error| CliError::failed_to_synthesize_witness("the inputs", error).into())
+}
+
+/// Returns the functions of the given program whose circuits are synthesized: all of them, or only `only_function`.
+/// The closures a function calls are part of its circuit, so they are synthesized with it.
+fn synthesized_functions(
+    program: &Program<Testnet3>,
+    only_function: Option<&str>,
+) -> Result<Vec<Identifier<Testnet3>>> {
+    let functions = program.functions().keys().copied();
+    let Some(only_function) = only_function else {
+        return Ok(functions.collect());
+    };
+    match functions.clone().find(|function_name| function_name.to_string() == only_function) {
+        Some(function_name) => Ok(vec![function_name]),
+        None => {
+            let names = functions.map(|function_name| format!("`{function_name}`")).collect::<Vec<_>>().join(", ");
+            Err(CliError::unknown_only_function(only_function, program.id(), names).into())
+        }
+    }
+}
+
+/// Returns the constraints of the given circuit, with its numbers of public and private variables.
+fn serialize_assignment(assignment: &Assignment<<Testnet3 as Environment>::Field>) -> SerializedFunctionCircuit {
+    let linear_combination = |lc: &ConstraintLC| SerializedLinearCombination {
+        constant: lc.constant().to_string(),
+        terms: lc
+            .terms()
+            .iter()
+            .map(|(variable, coefficient)| {
+                let variable = match variable {
+                    AssignmentVariable::Constant(value) => SerializedVariable::Constant(value.to_string()),
+                    AssignmentVariable::Public(index) => SerializedVariable::Public(*index),
+                    AssignmentVariable::Private(index) => SerializedVariable::Private(*index),
+                };
+                (variable, coefficient.to_string())
+            })
+            .collect(),
+    };
+    SerializedFunctionCircuit {
+        num_public: assignment.num_public(),
+        num_private: assignment.num_private(),
+        constraints: assignment
+            .constraints()
+            .iter()
+            .map(|(a, b, c)| [linear_combination(a), linear_combination(b), linear_combination(c)])
+            .collect(),
+    }
+}
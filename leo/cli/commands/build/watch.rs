@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Rebuilding a package on every change to its files, for `leo build --watch`.
+
+use super::*;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::sync::mpsc;
+
+/// The period without further file changes to wait for before rebuilding in watch mode.
+const WATCH_DEBOUNCE_PERIOD: Duration = Duration::from_millis(200);
+
+/// Rebuilds the package on every change to its source, import, or input files, until interrupted.
+/// Build errors are printed without stopping the watcher.
+pub(super) fn watch(options: BuildOptions, dry_run: bool, context: Context) -> Result<<Build as Command>::Output> {
+    let package_path = context.dir()?;
+    let source_directory = options.source_directory(&package_path, &ManifestConfig::read_from(&package_path)?.build)?;
+
+    // Watch the directories containing the files the build depends on.
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(CliError::failed_to_watch_files)?;
+    let directories = [IMPORTS_DIRECTORY_NAME, INPUTS_DIRECTORY_NAME].map(|directory| package_path.join(directory));
+    for directory in [source_directory].into_iter().chain(directories) {
+        if directory.exists() {
+            watcher.watch(&directory, RecursiveMode::Recursive).map_err(CliError::failed_to_watch_files)?;
+        }
+    }
+    if let Some(input_dir) = options.input_dir.as_ref().filter(|input_dir| input_dir.exists()) {
+        watcher.watch(input_dir, RecursiveMode::Recursive).map_err(CliError::failed_to_watch_files)?;
+    }
+
+    loop {
+        let start = Instant::now();
+        match (Build { dry_run, ..Build::new(options.clone()) }).apply(context.clone(), ()) {
+            Ok(_) => tracing::info!("Finished build in {} ms", start.elapsed().as_millis()),
+            Err(error) => {
+                // The errors of the compiler have already been printed.
+                if !matches!(error, LeoError::LastErrorCode(_)) {
+                    eprintln!("{error}");
+                }
+                tracing::info!("Failed build in {} ms", start.elapsed().as_millis())
+            }
+        }
+        tracing::info!("Watching for changes, press Ctrl-C to stop");
+
+        // Wait for a change to a Leo or input file.
+        while !is_watched_change(receiver.recv().map_err(CliError::failed_to_watch_files)?) {}
+
+        // Debounce rapid edits by waiting until the files stop changing.
+        while receiver.recv_timeout(WATCH_DEBOUNCE_PERIOD).is_ok() {}
+    }
+}
+
+/// Returns `true` if the file system event modifies a Leo or input file.
+fn is_watched_change(event: notify::Result<Event>) -> bool {
+    match event {
+        Ok(event) => {
+            !matches!(event.kind, EventKind::Access(_))
+                && event.paths.iter().any(|path| {
+                    let path = path.to_string_lossy();
+                    path.ends_with(LEO_FILE_EXTENSION) || path.ends_with(INPUT_FILE_EXTENSION)
+                })
+        }
+        // Rebuild on watcher errors, as changes may have been missed.
+        Err(_) => true,
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Building the members of a workspace, each after the members it imports.
+
+use super::*;
+
+/// Builds every member of the workspace at the root of the context, a member only after the members it imports.
+/// Stops at the first member that fails to build, and returns the output of the last member otherwise.
+/// With `keep_going`, the members which don't import a failed member are still built, and the failures are reported
+/// after all of them. With `since`, the members unchanged since the given git revision are skipped, unless they
+/// import a member which changed.
+pub(super) fn build_workspace(
+    options: BuildOptions,
+    dry_run: bool,
+    keep_going: bool,
+    since: Option<&str>,
+    context: Context,
+) -> Result<<Build as Command>::Output> {
+    let root = std::env::current_dir().map_err(CliError::cli_io_error)?.join(context.dir()?);
+    let config = ManifestConfig::read_from(&root)?;
+    if !config.is_workspace() {
+        return Err(CliError::not_a_workspace(root.join(MANIFEST_FILENAME).display()).into());
+    }
+
+    // Find the members imported by each member, by the names of their programs.
+    let members = &config.workspace.members;
+    let mut program_names = Vec::with_capacity(members.len());
+    let mut source_directories = Vec::with_capacity(members.len());
+    for member in members {
+        let member_config = ManifestConfig::read_from(&root.join(member))?;
+        program_names.push(member_config.program.trim_end_matches(".aleo").to_string());
+        source_directories.push(options.source_directory(&root.join(member), &member_config.build)?);
+    }
+    let mut dependencies = Vec::with_capacity(members.len());
+    for (member, source_directory) in members.iter().zip(&source_directories) {
+        let imports = imported_program_names(&root.join(member), source_directory)?;
+        dependencies.push(
+            program_names.iter().enumerate().filter(|(_, name)| imports.contains(name)).map(|(i, _)| i).collect(),
+        );
+    }
+
+    let order = workspace_build_order(members, &dependencies)?;
+    let selected = match since {
+        Some(git_ref) => match changed_members(&root, members, git_ref) {
+            Some(changed) => members_to_build(&order, &dependencies, &changed),
+            None => {
+                tracing::warn!("Building every workspace member, as git can't tell which changed since `{git_ref}`");
+                vec![true; members.len()]
+            }
+        },
+        None => vec![true; members.len()],
+    };
+
+    let mut summary = Vec::with_capacity(members.len());
+    let mut output = None;
+    let mut failed = vec![false; members.len()];
+    let mut skipped = 0;
+    for index in order {
+        let member = &members[index];
+
+        if let (false, Some(git_ref)) = (selected[index], since) {
+            tracing::info!("Skipping workspace member `{member}`, as it is unchanged since `{git_ref}`");
+            skipped += 1;
+            summary.push((member, format!("skipped, unchanged since `{git_ref}`")));
+            continue;
+        }
+
+        // A member importing a failed member would fail as well, so it is skipped.
+        if dependencies[index].iter().any(|&dependency| failed[dependency]) {
+            tracing::warn!("Skipping workspace member `{member}`, as it imports a member which failed to build");
+            failed[index] = true;
+            summary.push((member, "skipped, as it imports a member which failed".to_string()));
+            continue;
+        }
+        tracing::info!("Building workspace member `{member}`");
+
+        let member_path = root.join(member);
+        let start = Instant::now();
+        let build = Build { dry_run, ..Build::new(options.clone()) };
+        match build.apply(Context::new(Some(member_path.clone()))?, ()) {
+            Ok(member_output) => output = Some(member_output),
+            Err(error) => {
+                // The errors of the compiler have already been printed.
+                if !matches!(error, LeoError::LastErrorCode(_)) {
+                    eprintln!("{error}");
+                }
+                if !keep_going {
+                    return Err(CliError::workspace_member_failed(member).into());
+                }
+                tracing::error!("Failed to build the workspace member `{member}`");
+                failed[index] = true;
+                summary.push((member, "failed".to_string()));
+                continue;
+            }
+        }
+
+        // The number of instructions is only known if the compiled program was written.
+        let aleo_file_path = member_path.join(BUILD_DIRECTORY_NAME).join(MAIN_ALEO_FILE_NAME);
+        let instructions = read_aleo_file(&aleo_file_path).ok().map(|program| {
+            program.functions().values().map(|function| function.instructions().len()).sum::<usize>()
+                + program.closures().values().map(|closure| closure.instructions().len()).sum::<usize>()
+        });
+        let instructions = instructions.map_or("-".to_string(), |count| count.to_string());
+        summary.push((member, format!("{instructions} instructions, {} ms", start.elapsed().as_millis())));
+    }
+
+    let failures = members.iter().zip(&failed).filter(|(_, failed)| **failed).map(|(member, _)| member);
+    let failures = failures.map(|member| format!("`{member}`")).collect::<Vec<_>>();
+    tracing::info!("Built {} of {} workspace members", members.len() - failures.len() - skipped, members.len());
+    for (member, result) in summary {
+        tracing::info!("  {member}: {result}");
+    }
+    if !failures.is_empty() {
+        return Err(CliError::workspace_members_failed(failures.join(", ")).into());
+    }
+
+    // The members are not empty, so one of them has been built, unless all of them are unchanged.
+    Ok(output.unwrap_or_else(|| BuildOutput {
+        input_ast: None,
+        structs: IndexMap::new(),
+        tests: Vec::new(),
+        timings: BuildTimings::default(),
+        persisted: false,
+        recompiled: false,
+        bins: IndexMap::new(),
+    }))
+}
+
+/// Returns which of the given workspace members have files changed since the given git revision, committed or not,
+/// outside of their build and outputs directories. Returns `None` if git can't tell, e.g. outside of a repository.
+fn changed_members(root: &Path, members: &[String], git_ref: &str) -> Option<Vec<bool>> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = std::process::Command::new("git").arg("-C").arg(root).args(args).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    // The changed files are listed relative to the root of the repository, which may be above the workspace.
+    let toplevel = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?.trim());
+    let changed = git(&["diff", "--name-only", git_ref, "--", "."])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard", "--full-name", "--", "."])?;
+    let changed = changed.lines().chain(untracked.lines()).map(|path| toplevel.join(path)).collect::<Vec<_>>();
+
+    members
+        .iter()
+        .map(|member| {
+            let member_path = root.join(member).canonicalize().ok()?;
+            let generated = [BUILD_DIRECTORY_NAME, OUTPUTS_DIRECTORY_NAME].map(|directory| member_path.join(directory));
+            Some(changed.iter().any(|path| {
+                path.starts_with(&member_path) && !generated.iter().any(|directory| path.starts_with(directory))
+            }))
+        })
+        .collect()
+}
+
+/// Returns which workspace members to build, given which of them changed: the changed members, and the members
+/// importing a member which is built. `order` is the order in which the members are built.
+pub(crate) fn members_to_build(order: &[usize], dependencies: &[Vec<usize>], changed: &[bool]) -> Vec<bool> {
+    let mut selected = changed.to_vec();
+    for &index in order {
+        selected[index] |= dependencies[index].iter().any(|&dependency| selected[dependency]);
+    }
+    selected
+}
+
+/// Returns the order in which to build the workspace members, given the indices of the members each member imports.
+/// Members without a dependency on each other are built in the order in which they are listed.
+pub(crate) fn workspace_build_order(members: &[String], dependencies: &[Vec<usize>]) -> Result<Vec<usize>> {
+    let mut order = Vec::with_capacity(members.len());
+    while order.len() < members.len() {
+        // Build the first member whose imported members are all built.
+        let next = (0..members.len()).find(|index| {
+            !order.contains(index) && dependencies[*index].iter().all(|dependency| order.contains(dependency))
+        });
+        match next {
+            Some(index) => order.push(index),
+            None => {
+                let cycle = (0..members.len())
+                    .filter(|index| !order.contains(index))
+                    .map(|index| format!("`{}`", members[index]))
+                    .collect::<Vec<_>>();
+                return Err(CliError::workspace_dependency_cycle(cycle.join(", ")).into());
+            }
+        }
+    }
+    Ok(order)
+}
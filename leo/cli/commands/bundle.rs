@@ -0,0 +1,162 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::commands::build::COMPILER_VERSION;
+
+use leo_package::{
+    build::BUILD_DIRECTORY_NAME,
+    imports::IMPORTS_DIRECTORY_NAME,
+    outputs::{ChecksumAlgorithm, ChecksumFile, CircuitFile, MAIN_ALEO_FILE_NAME},
+    root::ManifestConfig,
+    write_atomically,
+};
+use snarkvm::file::Manifest;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Write},
+};
+
+/// The extension of the bundles written by `leo bundle`.
+pub(crate) static BUNDLE_FILE_EXTENSION: &str = ".leobundle";
+
+/// The file of a bundle listing its contents.
+pub(crate) static BUNDLE_METADATA_FILE_NAME: &str = "bundle.json";
+
+/// The version of the layout of bundles, which is increased whenever the layout changes.
+pub(crate) const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// The metadata of a bundle, stored in its `bundle.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleMetadata {
+    pub format_version: u32,
+    pub package_name: String,
+    pub compiler_version: String,
+    /// The checksums of the files of the bundle, by their path relative to the package.
+    pub files: BTreeMap<String, String>,
+}
+
+/// Pack the compiled programs, checksum, and circuit of the last build into a single file.
+#[derive(Parser, Debug)]
+pub struct Bundle {
+    #[clap(
+        long,
+        short,
+        value_name = "FILE",
+        help = "Writes the bundle to the given file instead of `outputs/{program}.leobundle`."
+    )]
+    pub(crate) output: Option<PathBuf>,
+}
+
+impl Command for Bundle {
+    type Input = ();
+    type Output = PathBuf;
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let package_path = context.dir()?;
+        let config = ManifestConfig::read_from(&package_path)?;
+        let manifest =
+            Manifest::<CurrentNetwork>::open(&package_path).map_err(PackageError::failed_to_open_manifest)?;
+        let package_name = manifest.program_id().name().to_string();
+
+        // The compiled program and its checksum are required, the imports and the circuit are bundled if they exist.
+        let build_directory = package_path.join(BUILD_DIRECTORY_NAME);
+        let outputs_directory = package_path.join(OUTPUTS_DIRECTORY_NAME);
+        let mut files = BTreeMap::new();
+        for path in [
+            build_directory.join(MAIN_ALEO_FILE_NAME),
+            outputs_directory.join(ChecksumFile::new(&package_name).file_name()),
+        ] {
+            let contents = std::fs::read(&path).map_err(|_| CliError::bundle_not_built(path.display()))?;
+            files.insert(bundle_path(&package_path, &path), contents);
+        }
+        let imports_directory = build_directory.join(IMPORTS_DIRECTORY_NAME);
+        if imports_directory.is_dir() {
+            let entries = std::fs::read_dir(&imports_directory)
+                .map_err(|e| PackageError::failed_to_read_directory(imports_directory.display(), e))?;
+            for entry in entries {
+                let path =
+                    entry.map_err(|e| PackageError::failed_to_read_directory(imports_directory.display(), e))?.path();
+                if path.extension().is_some_and(|extension| extension == "aleo") {
+                    let contents =
+                        std::fs::read(&path).map_err(|e| PackageError::failed_to_read_file(path.display(), e))?;
+                    files.insert(bundle_path(&package_path, &path), contents);
+                }
+            }
+        }
+        // A circuit is bundled uncompressed, with a content-addressed circuit resolved.
+        for compressed in [false, true] {
+            let circuit_file = CircuitFile::new(&package_name).with_compression(compressed);
+            if circuit_file.exists_at(&outputs_directory) {
+                let circuit = circuit_file.read_from(&outputs_directory)?;
+                files.insert(format!("{OUTPUTS_DIRECTORY_NAME}{package_name}.json"), circuit.into_bytes());
+                break;
+            }
+        }
+
+        let algorithm = config.build.checksum_algorithm;
+        let metadata = BundleMetadata {
+            format_version: BUNDLE_FORMAT_VERSION,
+            package_name: package_name.clone(),
+            compiler_version: COMPILER_VERSION.to_string(),
+            files: files.iter().map(|(name, contents)| (name.clone(), algorithm.checksum(contents))).collect(),
+        };
+        let metadata = serde_json::to_string_pretty(&metadata).expect("the metadata is serializable");
+
+        let bundle_path = match self.output {
+            Some(output) => output,
+            None => outputs_directory.join(format!("{package_name}{BUNDLE_FILE_EXTENSION}")),
+        };
+        let error = |e: &dyn std::fmt::Display| CliError::failed_to_write_bundle(bundle_path.display(), e);
+        // The entries have no timestamps, so that bundling the same build twice gives the same bundle.
+        let options = zip::write::FileOptions::default().last_modified_time(zip::DateTime::default());
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in std::iter::once((BUNDLE_METADATA_FILE_NAME, metadata.as_bytes()))
+            .chain(files.iter().map(|(name, contents)| (name.as_str(), contents.as_slice())))
+        {
+            writer.start_file(name, options).map_err(|e| error(&e))?;
+            writer.write_all(contents).map_err(|e| error(&e))?;
+        }
+        let bundle = writer.finish().map_err(|e| error(&e))?.into_inner();
+        write_atomically(&bundle_path, &bundle).map_err(|e| error(&e))?;
+
+        tracing::info!("✅ Bundled {} files of '{package_name}' into '{}'", files.len(), bundle_path.display());
+        Ok(bundle_path)
+    }
+}
+
+/// Returns the path of a file of the package in a bundle, relative to the package with `/` separators.
+fn bundle_path(package_path: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(package_path).unwrap_or(path);
+    relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+/// Returns the algorithm which computed the given checksum, from its prefix.
+pub(crate) fn checksum_algorithm(checksum: &str) -> Option<ChecksumAlgorithm> {
+    [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Blake3]
+        .into_iter()
+        .find(|algorithm| checksum.strip_prefix(&algorithm.to_string()).is_some_and(|hash| hash.starts_with(':')))
+}
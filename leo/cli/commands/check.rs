@@ -0,0 +1,96 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use crate::cli::commands::build::{import_files, program_name, resolve_import_paths, source_files};
+
+use leo_compiler::Compiler;
+use leo_package::{outputs::OUTPUTS_DIRECTORY_NAME, root::ManifestConfig};
+
+use snarkvm::file::Manifest;
+
+/// Check the current package for errors without generating Aleo instructions.
+#[derive(Parser, Debug)]
+pub struct Check {}
+
+impl Command for Check {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Get the package path.
+        let package_path = context.dir()?;
+
+        // Validate the program name, as snarkVM's errors for invalid names are obscure.
+        let config = ManifestConfig::read_from(&package_path)?;
+        config.validate()?;
+
+        // Open the manifest directly, as `Context::open_manifest` mirrors it into the build directory.
+        let manifest =
+            Manifest::<CurrentNetwork>::open(&package_path).map_err(PackageError::failed_to_open_manifest)?;
+        let program_id = manifest.program_id();
+
+        // Initialize error handler
+        let handler = Handler::default();
+
+        // Fetch paths to all .leo files in the source directory, except those ignored by `.leoignore`.
+        let source_directory = BuildOptions::default().source_directory(&package_path, &config.build)?;
+        let source_files = source_files(&package_path, &source_directory, false)?;
+
+        // Fetch paths to all .leo files in the imports directory, and to the imports mapped in the manifest.
+        let import_paths = resolve_import_paths(&package_path, &config.imports)?;
+        let import_files = import_files(&package_path, &source_directory, &import_paths, false)?;
+
+        // Check all .leo files, without code generation.
+        let files =
+            import_files.into_iter().map(|path| (path, true)).chain(source_files.into_iter().map(|path| (path, false)));
+        for (file_path, is_import) in files {
+            let file_name = file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(PackageError::failed_to_get_file_name)?
+                .to_string();
+
+            // Compiler snapshots are disabled, so nothing is written to the outputs directory.
+            let mut compiler = Compiler::new(
+                program_name(&file_name, program_id, is_import)?,
+                program_id.network().to_string(),
+                &handler,
+                file_path,
+                package_path.join(OUTPUTS_DIRECTORY_NAME),
+                None,
+            )
+            .with_import_paths(import_paths.clone());
+
+            // Run all compiler passes except code generation.
+            compiler.parse_program()?;
+            compiler.compiler_stages()?;
+
+            tracing::info!("✅ Checked '{}'", file_name);
+        }
+
+        Ok(())
+    }
+}
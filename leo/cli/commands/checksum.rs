@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use leo_package::{
+    outputs::{ChecksumFile, OUTPUTS_DIRECTORY_NAME},
+    root::ManifestConfig,
+    source::{MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+};
+
+use snarkvm::file::Manifest;
+
+/// Print the checksum of the current program, or verify it against the checksum of its last build.
+#[derive(Parser, Debug)]
+pub struct Checksum {
+    #[clap(long, help = "Compares the checksum of the program against the checksum stored by its last build.")]
+    pub(crate) verify: bool,
+    #[clap(
+        long,
+        value_name = "FILE_NAME",
+        value_parser = parse_output_file_name,
+        help = "Reads the stored checksum from the given file, as written by `leo build --checksum-name`."
+    )]
+    pub(crate) checksum_name: Option<String>,
+}
+
+impl Command for Checksum {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Get the package path.
+        let package_path = context.dir()?;
+
+        // The checksum is computed with the algorithm configured in the manifest, as it is by `leo build`.
+        let config = ManifestConfig::read_from(&package_path)?;
+        config.validate()?;
+        let manifest =
+            Manifest::<CurrentNetwork>::open(&package_path).map_err(PackageError::failed_to_open_manifest)?;
+        let program_name = manifest.program_id().name().to_string();
+
+        let main_file_path = package_path.join(SOURCE_DIRECTORY_NAME).join(MAIN_FILENAME);
+        let source = std::fs::read(&main_file_path).map_err(CliError::cli_io_error)?;
+        let checksum = config.build.checksum_algorithm.checksum(&source);
+
+        if !self.verify {
+            println!("{checksum}");
+            return Ok(());
+        }
+
+        // The first line of the checksum file is the checksum of the program alone.
+        let checksum_file = match &self.checksum_name {
+            Some(checksum_name) => ChecksumFile::new(&program_name).with_file_name(checksum_name),
+            None => ChecksumFile::new(&program_name),
+        };
+        let checksum_path = package_path.join(OUTPUTS_DIRECTORY_NAME).join(checksum_file.file_name());
+        let stored_checksum = checksum_file.read_program_checksum(&checksum_path)?;
+        if stored_checksum != checksum {
+            return Err(CliError::checksum_mismatch(checksum_path.display(), stored_checksum, checksum).into());
+        }
+
+        tracing::info!("✅ The checksum of '{MAIN_FILENAME}' matches '{}'", checksum_path.display());
+
+        Ok(())
+    }
+}
@@ -16,9 +16,20 @@
 
 use super::*;
 
+use leo_package::{
+    imports::IMPORTS_DIRECTORY_NAME,
+    outputs::OUTPUTS_DIRECTORY_NAME,
+    root::{ManifestConfig, MANIFEST_FILENAME},
+};
+
+use std::path::Path;
+
 /// Clean outputs folder command
 #[derive(Parser, Debug)]
-pub struct Clean {}
+pub struct Clean {
+    #[clap(long, help = "Removes the entire outputs directory instead of only the generated files.")]
+    pub(crate) all: bool,
+}
 
 impl Command for Clean {
     type Input = ();
@@ -35,8 +46,15 @@ impl Command for Clean {
     fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
         let path = context.dir()?;
 
-        // Removes the outputs/ directory.
-        let outputs_path = OutputsDirectory::remove(&path)?;
+        // Refuse to remove anything outside of the package, e.g. if a directory is a symbolic link.
+        ensure_within_package(&path.join(OUTPUTS_DIRECTORY_NAME), &path)?;
+        ensure_within_package(&path.join(BUILD_DIRECTORY_NAME), &path)?;
+
+        // Removes the generated files, or the entire outputs/ directory if `--all` is set.
+        let outputs_path = match self.all {
+            true => OutputsDirectory::remove(&path)?,
+            false => OutputsDirectory::remove_generated_files(&path, &generated_file_names(&path)?)?,
+        };
         tracing::info!("🧹 Cleaned the outputs directory {}", outputs_path.dimmed());
 
         // Removes the build/ directory.
@@ -46,3 +64,42 @@ impl Command for Clean {
         Ok(())
     }
 }
+
+/// Returns an error if the directory at the given path exists and resolves to a path outside of the package.
+fn ensure_within_package(directory: &Path, package_path: &Path) -> Result<()> {
+    if !directory.exists() {
+        return Ok(());
+    }
+
+    let directory = directory.canonicalize().map_err(CliError::cli_io_error)?;
+    let package_path = package_path.canonicalize().map_err(CliError::cli_io_error)?;
+    match directory.starts_with(package_path) {
+        true => Ok(()),
+        false => Err(CliError::directory_outside_of_package(directory.display()).into()),
+    }
+}
+
+/// Returns the names the files generated for the package are named after: those of its program, of its entry
+/// files, and of the imported programs of its last build.
+fn generated_file_names(package_path: &Path) -> Result<Vec<String>> {
+    let config = match package_path.join(MANIFEST_FILENAME).exists() {
+        true => ManifestConfig::read_from(package_path)?,
+        false => ManifestConfig::synthetic(package_path)?,
+    };
+    let mut names = vec![config.program.trim_end_matches(".aleo").to_string()];
+    names.extend(config.bins.iter().filter_map(|bin| file_stem(&bin.path)));
+
+    let imports_path = package_path.join(BUILD_DIRECTORY_NAME).join(IMPORTS_DIRECTORY_NAME);
+    if imports_path.is_dir() {
+        let entries = std::fs::read_dir(&imports_path).map_err(CliError::cli_io_error)?;
+        for entry in entries {
+            names.extend(file_stem(&entry.map_err(CliError::cli_io_error)?.path()));
+        }
+    }
+    Ok(names)
+}
+
+/// Returns the name of the file at the given path, without its extension.
+fn file_stem(path: &Path) -> Option<String> {
+    path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string)
+}
@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::commands::inspect::read_program;
+
+use leo_package::write_atomically;
+use snarkvm::prelude::ToBytes;
+
+/// Convert a compiled program between Aleo instructions and the binary format of `.avm` files.
+#[derive(Parser, Debug)]
+pub struct Convert {
+    #[clap(name = "IN", help = "The compiled program to convert, in either format")]
+    pub(crate) input: PathBuf,
+
+    #[clap(name = "OUT", help = "The file to write, in the format of its extension: `.aleo` or `.avm`")]
+    pub(crate) output: PathBuf,
+}
+
+impl Command for Convert {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        let bytes =
+            std::fs::read(&self.input).map_err(|err| CliError::invalid_program_file(self.input.display(), err))?;
+        let (program, format) =
+            read_program(&bytes).map_err(|err| CliError::invalid_program_file(self.input.display(), err))?;
+
+        let converted = match self.output.extension().and_then(|extension| extension.to_str()) {
+            Some("aleo") => program.to_string().into_bytes(),
+            Some("avm") => program.to_bytes_le().map_err(CliError::failed_to_write_file)?,
+            _ => return Err(CliError::unknown_program_format(self.output.display()).into()),
+        };
+
+        // The written file must read back as the same program, so that the conversion loses nothing.
+        write_atomically(&self.output, &converted).map_err(CliError::failed_to_write_file)?;
+        let (round_trip, converted_format) =
+            read_program(&converted).map_err(|err| CliError::invalid_program_file(self.output.display(), err))?;
+        if round_trip != program {
+            return Err(CliError::invalid_program_file(
+                self.output.display(),
+                "the converted program differs from the original",
+            )
+            .into());
+        }
+
+        tracing::info!(
+            "✅ Converted '{}' from {format} to {converted_format} in '{}'",
+            self.input.display(),
+            self.output.display()
+        );
+        Ok(())
+    }
+}
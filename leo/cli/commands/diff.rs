@@ -0,0 +1,90 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use leo_package::outputs::StatsFile;
+
+use serde_json::{Map, Value};
+
+/// Compare the statistics of two builds, written with `leo build --emit-stats`.
+#[derive(Parser, Debug)]
+pub struct Diff {
+    #[clap(name = "OLD", help = "The statistics file of the old build")]
+    old: PathBuf,
+
+    #[clap(name = "NEW", help = "The statistics file of the new build")]
+    new: PathBuf,
+}
+
+impl Command for Diff {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Diff")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        let old = read_stats(&self.old)?;
+        let new = read_stats(&self.new)?;
+
+        // Report every statistic of either build, in the order they are written.
+        let mut changes = vec![];
+        for key in old.keys().chain(new.keys().filter(|key| !old.contains_key(*key))) {
+            let old_count = old.get(key).and_then(Value::as_u64).unwrap_or_default();
+            let new_count = new.get(key).and_then(Value::as_u64).unwrap_or_default();
+            if old_count != new_count {
+                changes.push(format!("{key}: {old_count} -> {new_count} ({})", format_change(old_count, new_count)));
+            }
+        }
+
+        match changes.is_empty() {
+            true => tracing::info!("✅ '{}' and '{}' are identical", self.old.display(), self.new.display()),
+            false => {
+                tracing::info!("Changes from '{}' to '{}':", self.old.display(), self.new.display());
+                for change in changes {
+                    tracing::info!("  {change}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the statistics written by `leo build --emit-stats` at the given path.
+fn read_stats(path: &Path) -> Result<Map<String, Value>> {
+    let stats = StatsFile::new("").read_from(path)?;
+    match serde_json::from_str(&stats) {
+        Ok(Value::Object(stats)) => Ok(stats),
+        Ok(_) => Err(CliError::invalid_stats_file(path.display(), "expected a JSON object").into()),
+        Err(err) => Err(CliError::invalid_stats_file(path.display(), err).into()),
+    }
+}
+
+/// Formats the change between two counts, e.g. `+2, +20.00%`.
+pub(crate) fn format_change(old: u64, new: u64) -> String {
+    let delta = new as i128 - old as i128;
+    match old {
+        0 => format!("{delta:+}"),
+        _ => format!("{delta:+}, {:+.2}%", delta as f64 * 100.0 / old as f64),
+    }
+}
@@ -35,7 +35,7 @@ impl Command for Example {
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
         // Run leo new EXAMPLE_NAME
-        (New { name: self.name() }).execute(context)
+        (New { name: self.name(), template: PackageTemplate::default() }).execute(context)
     }
 
     fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output>
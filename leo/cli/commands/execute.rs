@@ -15,6 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
+use crate::cli::{commands::build::input_file_inputs, helpers::offline::is_offline};
 
 use snarkvm::cli::Execute as SnarkVMExecute;
 
@@ -48,17 +49,14 @@ impl Command for Execute {
     }
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
-        (Build { options: self.compiler_options.clone() }).execute(context)
+        Build::new(self.compiler_options.clone()).execute(context)
     }
 
     fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
         // If input values are provided, then run the program with those inputs.
         // Otherwise, use the input file.
         let mut inputs = match self.inputs.is_empty() {
-            true => match input {
-                (Some(input_ast), circuits) => input_ast.program_inputs(&self.name, circuits),
-                _ => Vec::new(),
-            },
+            true => input_file_inputs(input, &self.name, &context.dir()?)?,
             false => self.inputs,
         };
 
@@ -69,7 +67,7 @@ impl Command for Execute {
         arguments.append(&mut inputs);
 
         // Add the compiler options to the arguments.
-        if self.compiler_options.offline {
+        if is_offline() {
             arguments.push(String::from("--offline"));
         }
 
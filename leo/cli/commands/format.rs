@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use leo_ast::NodeBuilder;
+use leo_package::source::SourceDirectory;
+use leo_span::symbol::with_session_globals;
+
+/// Format the Leo source files of the current package.
+#[derive(Parser, Debug)]
+pub struct Format {
+    #[clap(long, help = "Checks that the files are formatted without modifying them, failing otherwise.")]
+    pub(crate) check: bool,
+}
+
+impl Command for Format {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Get the package path.
+        let package_path = context.dir()?;
+
+        // Initialize error handler
+        let handler = Handler::default();
+
+        // Format each .leo file in the source directory.
+        let mut unformatted = 0;
+        for file_path in SourceDirectory::files(&package_path)? {
+            let source_file = with_session_globals(|s| s.source_map.load_file(&file_path))
+                .map_err(|e| CompilerError::file_read_error(&file_path, e))?;
+            let formatted =
+                leo_parser::format_source(&handler, &NodeBuilder::default(), &source_file.src, source_file.start_pos)?;
+
+            if formatted == source_file.src {
+                continue;
+            }
+            unformatted += 1;
+
+            match self.check {
+                true => tracing::info!("'{}' is not formatted", file_path.display()),
+                false => {
+                    std::fs::write(&file_path, formatted).map_err(CliError::failed_to_write_file)?;
+                    tracing::info!("✅ Formatted '{}'", file_path.display());
+                }
+            }
+        }
+
+        if self.check && unformatted > 0 {
+            return Err(CliError::unformatted_files(unformatted).into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::{FromBytes, Program};
+use std::str::FromStr;
+
+/// Print a summary of a compiled program, read from Aleo instructions or from a binary `.avm` file.
+#[derive(Parser, Debug)]
+pub struct Inspect {
+    #[clap(name = "FILE", help = "The compiled program to inspect, such as `build/main.aleo`")]
+    pub(crate) file: PathBuf,
+}
+
+impl Command for Inspect {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        let bytes =
+            std::fs::read(&self.file).map_err(|err| CliError::invalid_program_file(self.file.display(), err))?;
+        let (program, format) =
+            read_program(&bytes).map_err(|err| CliError::invalid_program_file(self.file.display(), err))?;
+
+        tracing::info!("Program '{}' ({format})", program.id());
+        for import in program.imports().keys() {
+            tracing::info!("  import {import}");
+        }
+        tracing::info!(
+            "  {} structs, {} records, {} mappings",
+            program.structs().len(),
+            program.records().len(),
+            program.mappings().len()
+        );
+        for (name, closure) in program.closures() {
+            tracing::info!(
+                "  closure {name}: {} inputs, {} outputs, {} instructions",
+                closure.inputs().len(),
+                closure.outputs().len(),
+                closure.instructions().len()
+            );
+        }
+        for (name, function) in program.functions() {
+            let finalize = match function.finalize_logic() {
+                Some(finalize) => format!(", finalize with {} commands", finalize.commands().len()),
+                None => String::new(),
+            };
+            tracing::info!(
+                "  function {name}: {} inputs, {} outputs, {} instructions{finalize}",
+                function.inputs().len(),
+                function.outputs().len(),
+                function.instructions().len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a program from Aleo instructions, or from the bytes written to `.avm` files, by sniffing the contents.
+/// The bytes of a small program may be valid UTF-8, so they are decoded if they don't parse as instructions.
+/// Returns the program and the name of its format.
+pub(crate) fn read_program(bytes: &[u8]) -> std::result::Result<(Program<CurrentNetwork>, &'static str), String> {
+    let instructions = std::str::from_utf8(bytes).map_err(|err| err.to_string()).and_then(|instructions| {
+        Program::from_str(instructions).map(|program| (program, "Aleo instructions")).map_err(|err| err.to_string())
+    });
+    instructions.or_else(|instructions_error| match Program::from_bytes_le(bytes) {
+        Ok(program) => Ok((program, "AVM bytecode")),
+        // The error of the format the file looks like is reported.
+        Err(err) => Err(match std::str::from_utf8(bytes) {
+            Ok(_) => instructions_error,
+            Err(_) => err.to_string(),
+        }),
+    })
+}
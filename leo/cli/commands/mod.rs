@@ -17,12 +17,30 @@
 pub mod account;
 pub use account::Account;
 
+pub mod audit;
+pub use audit::Audit;
+
 pub mod build;
-pub use build::Build;
+pub use build::{build_package, Build, BuildOutput, BuildTimings};
+
+pub mod bundle;
+pub use bundle::Bundle;
+
+pub mod check;
+pub use check::Check;
+
+pub mod checksum;
+pub use checksum::Checksum;
 
 pub mod clean;
 pub use clean::Clean;
 
+pub mod convert;
+pub use convert::Convert;
+
+pub mod diff;
+pub use diff::Diff;
+
 pub mod example;
 pub use example::Example;
 
@@ -32,25 +50,69 @@ pub use execute::Execute;
 // pub mod deploy;
 // pub use deploy::Deploy;
 
+pub mod explain;
+pub use explain::Explain;
+
+pub mod format;
+pub use format::Format;
+
+pub mod inspect;
+pub use inspect::Inspect;
+
 pub mod new;
 pub use new::New;
 
 // pub mod node;
 // pub use node::Node;
 
+pub mod prove;
+pub use prove::Prove;
+
 pub mod run;
 pub use run::Run;
 
+pub mod schema;
+pub use schema::Schema;
+
+pub mod setup;
+pub use setup::Setup;
+
+pub mod test;
+pub use test::Test;
+
+pub mod unbundle;
+pub use unbundle::Unbundle;
+
 pub mod update;
 pub use update::Update;
 
+pub mod verify;
+pub use verify::Verify;
+
 use super::*;
 use crate::cli::helpers::context::*;
-use leo_errors::{emitter::Handler, CliError, CompilerError, PackageError, Result};
-use leo_package::{build::*, outputs::OutputsDirectory, package::*};
+use leo_errors::{
+    emitter::{Handler, JsonEmitter, SarifEmitter},
+    CliError,
+    CompilerError,
+    LeoError,
+    PackageError,
+    Result,
+};
+use leo_package::{
+    build::*,
+    inputs::INPUT_FILE_EXTENSION,
+    outputs::{OutputsDirectory, OUTPUTS_DIRECTORY_NAME},
+    package::*,
+    root::{BuildConfig, CompilerConfig},
+    source::SOURCE_DIRECTORY_NAME,
+};
 
 use clap::Parser;
 use colored::Colorize;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use std::path::{Path, PathBuf};
 use tracing::span::Span;
 
 /// Base trait for the Leo CLI, see methods and their documentation for details.
@@ -115,12 +177,308 @@ pub trait Command {
     }
 }
 
+/// Parses the name of a file in the outputs directory, which must not contain a path separator.
+pub(crate) fn parse_output_file_name(file_name: &str) -> std::result::Result<String, String> {
+    match file_name {
+        "" | "." | ".." => Err(format!("`{file_name}` is not a file name")),
+        _ if file_name.contains(['/', '\\']) => Err(format!("`{file_name}` contains a path separator")),
+        _ => Ok(file_name.to_string()),
+    }
+}
+
+/// Reads the options of the given ids from environment variables named after them with the given prefix, in the
+/// command and in each of its subcommands taking all of them. Options of other commands sharing an id, e.g.
+/// `leo account new --seed`, aren't.
+fn with_env_vars(mut command: clap::Command, ids: &[&clap::Id], prefix: &str) -> clap::Command {
+    if ids.iter().all(|id| command.get_arguments().any(|arg| arg.get_id() == *id)) {
+        command = command.mut_args(|arg| match (arg.get_long(), arg.get_env()) {
+            (Some(long), None) if ids.contains(&arg.get_id()) => {
+                let name = format!("{prefix}{}", long.to_uppercase().replace('-', "_"));
+                match arg.get_action() {
+                    clap::ArgAction::SetTrue => arg.env(name).value_parser(clap::builder::BoolishValueParser::new()),
+                    _ => arg.env(name),
+                }
+            }
+            _ => arg,
+        });
+    }
+    let subcommands = command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()).collect::<Vec<_>>();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |subcommand| with_env_vars(subcommand, ids, prefix));
+    }
+    command
+}
+
+/// The format in which the compiler prints errors and warnings.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Messages pointing to the offending source lines.
+    #[default]
+    Human,
+    /// One JSON object per message, in the format of the rustc JSON diagnostics.
+    Json,
+    /// Messages pointing to the offending source lines, which are also written as a SARIF log to `--sarif-out`.
+    Sarif,
+}
+
+impl MessageFormat {
+    /// Returns a handler printing messages in this format, collecting them into `sarif` in the SARIF format.
+    pub(crate) fn handler(self, sarif: &SarifEmitter) -> Handler {
+        match self {
+            MessageFormat::Human => Handler::default(),
+            MessageFormat::Json => Handler::new(Box::new(JsonEmitter::new())),
+            MessageFormat::Sarif => Handler::new(Box::new(sarif.clone())),
+        }
+    }
+}
+
+/// The format of the input file of a program.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// The native `.in` format, with a section of typed inputs for each function.
+    #[default]
+    Leo,
+    /// A JSON object mapping each transition to an object of its inputs by name, as described by `leo schema`.
+    Json,
+    /// A TOML table for each transition, holding its inputs by name.
+    Toml,
+}
+
+impl InputFormat {
+    /// Returns the extension of the input files in this format.
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            InputFormat::Leo => INPUT_FILE_EXTENSION,
+            InputFormat::Json => ".json",
+            InputFormat::Toml => ".toml",
+        }
+    }
+}
+
+/// The curves a program can be compiled for.
+/// The curve is fixed by the snarkVM network, so only the curve of `CurrentNetwork` is compiled in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Curve {
+    /// The BLS12-377 curve used by Testnet3.
+    #[default]
+    Bls12_377,
+}
+
+impl Curve {
+    /// The names of the supported curves.
+    const SUPPORTED: &'static [&'static str] = &["bls12-377"];
+
+    /// Returns the curve with the given name, if it is supported.
+    pub(crate) fn from_name(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "bls12-377" => Ok(Curve::Bls12_377),
+            _ => Err(CliError::unsupported_curve(name, Self::SUPPORTED.join(", ")).into()),
+        }
+    }
+}
+
 /// Compiler Options wrapper for Build command. Also used by other commands which
 /// require Build command output as their input.
+///
+/// Each option can also be given by an environment variable, as described by [`BuildOptions::with_env_vars`].
+/// A flag on the command line takes precedence over its environment variable, which takes precedence over the
+/// defaults of the `compiler` section of the manifest.
 #[derive(Parser, Clone, Debug, Default)]
 pub struct BuildOptions {
-    #[clap(long, help = "Enables offline mode.")]
-    pub offline: bool,
+    #[clap(
+        long,
+        help = "Compiles the given Leo file in `src/` instead of `main.leo`. It must declare the program in `program.json`."
+    )]
+    pub entry: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "NAME",
+        conflicts_with = "entry",
+        help = "Builds only the entry file of the given name from the `bins` section of `program.json`."
+    )]
+    pub bin: Option<String>,
+    #[clap(long = "input", value_name = "NAME", help = "Reads the program input from `inputs/{program}.NAME.in`.")]
+    pub input_name: Option<String>,
+    #[clap(long, value_name = "PATH", help = "Reads the input file from the given directory instead of `inputs/`.")]
+    pub input_dir: Option<PathBuf>,
+    #[clap(
+        long,
+        value_enum,
+        default_value_t,
+        help = "Reads the input file in the `.in` format, or as JSON or TOML from `{program}.json` or `{program}.toml`, mapping the inputs of each transition by name."
+    )]
+    pub input_format: InputFormat,
+    #[clap(
+        long,
+        conflicts_with_all = ["entry", "bin", "input_name", "input_dir", "max_constraints"],
+        help = "Compiles and checks only `src/lib.leo` for publishing, without reading inputs or synthesizing constraints."
+    )]
+    pub lib_only: bool,
+    #[clap(long, help = "The curve to compile the program for. Defaults to `bls12-377`, the only supported curve.")]
+    pub curve: Option<String>,
+    #[clap(long, overrides_with = "no_deny_warnings", help = "Fails the build if the compiler emits any warnings.")]
+    pub deny_warnings: bool,
+    #[clap(
+        long,
+        overrides_with = "deny_warnings",
+        help = "Doesn't fail the build on compiler warnings, even if `deny_warnings` is set in the manifest."
+    )]
+    pub no_deny_warnings: bool,
+    #[clap(
+        long,
+        value_enum,
+        default_value_t,
+        help = "Prints compiler errors and warnings as text or as lines of JSON, or also writes them as a SARIF log."
+    )]
+    pub message_format: MessageFormat,
+    #[clap(
+        long,
+        value_name = "PATH",
+        required_if_eq("message_format", "sarif"),
+        help = "Writes the SARIF log of `--message-format sarif` to the given path."
+    )]
+    pub sarif_out: Option<PathBuf>,
+    #[clap(long, help = "Writes compiler outputs to the given directory instead of `outputs/`.")]
+    pub output_dir: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Reads the Leo files of the program from the given directory instead of `src/`."
+    )]
+    pub source_dir: Option<PathBuf>,
+    #[clap(long, help = "Compresses the cached ASTs of imported programs and the circuit file with gzip.")]
+    pub compress: bool,
+    #[clap(
+        long,
+        value_name = "FILE_NAME",
+        value_parser = parse_output_file_name,
+        help = "Writes the checksum of the program to the given file in the outputs directory instead of `{program}.sum`."
+    )]
+    pub checksum_name: Option<String>,
+    #[clap(long, help = "Writes program statistics as JSON to the outputs directory.")]
+    pub emit_stats: bool,
+    #[clap(long, help = "Logs the peak memory usage of the build, on platforms which report it.")]
+    pub report_memory: bool,
+    #[clap(
+        long,
+        help = "Reports the instruction sequences repeated most often in the compiled program, as candidates for refactoring."
+    )]
+    pub analyze: bool,
+    #[clap(long, help = "Writes a Graphviz graph of the imports between the Leo files to the outputs directory.")]
+    pub emit_depgraph: bool,
+    #[clap(long, help = "Writes a human-readable report of the size of each function to the outputs directory.")]
+    pub emit_report: bool,
+    #[clap(
+        long,
+        conflicts_with = "lib_only",
+        help = "Synthesizes the circuits of the program and writes the number of constraints of each function, split between its inputs, its body, and its outputs, to the outputs directory."
+    )]
+    pub emit_constraint_report: bool,
+    #[clap(
+        long,
+        conflicts_with = "lib_only",
+        help = "Synthesizes the circuit of each function on the inputs of its section of the input file, and writes the values of its variables to `outputs/{program}.witness.json`. The file may contain private values, such as private inputs and the contents of records."
+    )]
+    pub emit_witness: bool,
+    #[clap(long, help = "Also writes the compiled programs in the binary AVM format to the build directory.")]
+    pub emit_avm: bool,
+    #[clap(
+        long,
+        overrides_with = "no_verify_serialization",
+        help = "Reads back each written AVM file to check that it decodes to the compiled program. This is the default."
+    )]
+    pub verify_serialization: bool,
+    #[clap(long, overrides_with = "verify_serialization", help = "Writes AVM files without reading them back.")]
+    pub no_verify_serialization: bool,
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        value_parser = ["-"],
+        help = "Writes the parsed AST of each program, with spans, to `outputs/{program}.ast.json`. `--print-ast=-` prints it to stdout."
+    )]
+    pub print_ast: Option<Option<String>>,
+    #[clap(
+        long,
+        num_args = 0..=1,
+        value_parser = ["-"],
+        conflicts_with = "lib_only",
+        help = "Synthesizes the circuits of the program and writes them as JSON to `outputs/{program}.json`. `--circuit-out -` prints them to stdout, with all logs on stderr."
+    )]
+    pub circuit_out: Option<Option<String>>,
+    #[clap(
+        long,
+        requires = "circuit_out",
+        help = "Writes the circuit file once to `.circuits/{sha256}.json` in the outputs directory, with a pointer to it in place of the file, so that the packages sharing an output directory store identical circuits once."
+    )]
+    pub content_addressed: bool,
+    #[clap(
+        long,
+        value_name = "NAME",
+        conflicts_with = "lib_only",
+        help = "Synthesizes only the circuit of the given function, with the closures it calls, to check its constraints and for `--emit-constraint-report`. The circuit is marked as partial and written to `outputs/{program}.{function}.partial.json`, or printed by `--circuit-out -`, never to the circuit file of the program."
+    )]
+    pub only_function: Option<String>,
+    #[clap(
+        long,
+        value_name = "SECONDS",
+        help = "Fails the build at its next step once it has taken longer than the given number of seconds, leaving previous artifacts intact."
+    )]
+    pub timeout: Option<u64>,
+    #[clap(
+        long,
+        value_name = "N",
+        help = "Fails the build if a function of the program synthesizes to more than the given number of constraints."
+    )]
+    pub max_constraints: Option<u64>,
+    #[clap(
+        long,
+        help = "Seeds the random number generator used to synthesize circuits and to prove executions, so that repeated runs are reproducible."
+    )]
+    pub seed: Option<u64>,
+    #[clap(
+        long,
+        value_name = "BYTES",
+        help = "Warns if a compiled program is larger than the given number of bytes of Aleo instructions."
+    )]
+    pub warn_program_size: Option<u64>,
+    #[clap(long, help = "Reuses compiled programs and cached ASTs built by a different version of Leo.")]
+    pub allow_stale: bool,
+    #[clap(
+        long,
+        help = "Reuses the cached compiled programs of the imports without checking whether they have changed. This is an optimization, not a correctness guarantee: a changed import is only recompiled if its cache is missing."
+    )]
+    pub skip_imports: bool,
+    #[clap(
+        long,
+        help = "Fails the build if a cached checksum or AST is corrupt, instead of removing it and recompiling."
+    )]
+    pub no_cache_recovery: bool,
+    #[clap(
+        long,
+        help = "Fails the build if a program compiles to other instructions than those in the build directory, instead of writing them. Checks in CI that the committed build matches the source."
+    )]
+    pub frozen: bool,
+    #[clap(long, help = "Builds a package without a manifest as a program named after its directory.")]
+    pub no_manifest: bool,
+    #[clap(long, help = "Lists the source and import files excluded from compilation by `.leoignore`.")]
+    pub list_ignored: bool,
+    #[clap(long, help = "Removes the checksum and circuit files of other programs from the outputs directory.")]
+    pub prune: bool,
+    #[clap(
+        long,
+        value_name = "COMMAND",
+        help = "Runs the given shell command after a build which compiled a program, overriding `hooks.post_build` in `program.json`."
+    )]
+    pub post_build: Option<String>,
+    #[clap(long, help = "Only warns if the post-build hook fails, instead of failing the build.")]
+    pub hook_best_effort: bool,
+    #[clap(
+        long,
+        value_name = "REV",
+        help = "Builds the package as it is at the given git revision, leaving the working tree untouched. The outputs and the compiled program are written to `outputs/`, or to `--output-dir` if it is given."
+    )]
+    pub git_ref: Option<String>,
     #[clap(long, help = "Enable spans in AST snapshots.")]
     pub enable_symbol_table_spans: bool,
     #[clap(long, help = "Enables dead code elimination in the compiler.")]
@@ -131,8 +489,14 @@ pub struct BuildOptions {
     pub enable_unrolled_symbol_table_snapshot: bool,
     #[clap(long, help = "Enable spans in AST snapshots.")]
     pub enable_ast_spans: bool,
-    #[clap(long, help = "Enable spans in symbol table snapshots.")]
+    #[clap(long, overrides_with = "disable_dce", help = "Enable spans in symbol table snapshots.")]
     pub enable_dce: bool,
+    #[clap(
+        long,
+        overrides_with = "enable_dce",
+        help = "Disables dead code elimination, even if `optimize` is set in the manifest."
+    )]
+    pub disable_dce: bool,
     #[clap(long, help = "Writes all AST snapshots for the different compiler phases.")]
     pub enable_all_ast_snapshots: bool,
     #[clap(long, help = "Writes Input AST snapshot of the initial parse.")]
@@ -152,3 +516,109 @@ pub struct BuildOptions {
     #[clap(long, help = "Writes AST snapshot of the dead code eliminated (DCE) AST.")]
     pub enable_dce_ast_snapshot: bool,
 }
+
+impl BuildOptions {
+    /// Returns the given command, with each of these options in it or in its subcommands taking them also read from
+    /// `LEO_` followed by the option in upper snake case, e.g. `LEO_OUTPUT_DIR` for `--output-dir`, unless it has its
+    /// own environment variable. Flags are read as booleans, e.g. `LEO_DENY_WARNINGS=1`.
+    pub(crate) fn with_env_vars(command: clap::Command) -> clap::Command {
+        Self::with_env_var_prefix(command, "LEO_")
+    }
+
+    /// Returns the given command, with these options read from environment variables with the given prefix in place
+    /// of `LEO_`, as [`BuildOptions::with_env_vars`] does.
+    pub(crate) fn with_env_var_prefix(command: clap::Command, prefix: &str) -> clap::Command {
+        let options = <Self as clap::CommandFactory>::command();
+        let ids = options.get_arguments().map(|arg| arg.get_id()).collect::<Vec<_>>();
+        with_env_vars(command, &ids, prefix)
+    }
+
+    /// Returns the curve to compile for, which is BLS12-377 unless another supported curve is given.
+    pub(crate) fn curve(&self) -> Result<Curve> {
+        self.curve.as_deref().map_or(Ok(Curve::default()), Curve::from_name)
+    }
+
+    /// Returns the options with the defaults of the `compiler` section of the manifest for the flags not given.
+    pub(crate) fn with_manifest_defaults(&self, config: &CompilerConfig) -> Self {
+        let mut options = self.clone();
+        options.enable_dce = !options.disable_dce && (options.enable_dce || config.optimize);
+        options.deny_warnings = !options.no_deny_warnings && (options.deny_warnings || config.deny_warnings);
+        if options.curve.is_none() {
+            options.curve = config.curve.clone();
+        }
+        if options.max_constraints.is_none() {
+            options.max_constraints = config.max_constraints;
+        }
+        if options.warn_program_size.is_none() {
+            options.warn_program_size = config.warn_program_size;
+        }
+        options
+    }
+
+    /// Returns the directory holding the `build/` directory. This is the package, unless the package directory is
+    /// read-only and a custom output directory is given, in which case it is the output directory.
+    pub(crate) fn build_root(&self, package_path: &Path) -> Result<PathBuf> {
+        let read_only = std::fs::metadata(package_path).is_ok_and(|metadata| metadata.permissions().readonly());
+        match self.output_dir.is_some() && read_only {
+            true => self.outputs_directory(package_path),
+            false => Ok(package_path.to_path_buf()),
+        }
+    }
+
+    /// Returns the directory the compiler outputs are written to, resolving a custom output directory against the cwd.
+    pub(crate) fn outputs_directory(&self, package_path: &Path) -> Result<PathBuf> {
+        match &self.output_dir {
+            Some(output_dir) => Ok(std::env::current_dir().map_err(CliError::cli_io_error)?.join(output_dir)),
+            None => Ok(package_path.join(OUTPUTS_DIRECTORY_NAME)),
+        }
+    }
+
+    /// Returns the directory holding the Leo files of the program. This is `src/`, unless a directory is given by
+    /// `--source-dir`, resolved against the cwd, or by `source_dir` in the manifest, resolved against the package.
+    pub(crate) fn source_directory(&self, package_path: &Path, config: &BuildConfig) -> Result<PathBuf> {
+        let source_directory = match (&self.source_dir, &config.source_dir) {
+            (Some(source_dir), _) => std::env::current_dir().map_err(CliError::cli_io_error)?.join(source_dir),
+            (None, Some(source_dir)) => package_path.join(source_dir),
+            (None, None) => return Ok(package_path.join(SOURCE_DIRECTORY_NAME)),
+        };
+        match source_directory.is_dir() {
+            true => Ok(source_directory),
+            false => Err(CliError::source_directory_not_found(source_directory.display()).into()),
+        }
+    }
+
+    /// Returns the random number generator, seeded by `--seed` if it is given, and from entropy otherwise.
+    /// It is used to synthesize the circuits checked by `--max-constraints`, and by `leo prove` and `leo test` to
+    /// run and prove the functions of the program. The keys written by `leo setup` are synthesized by snarkVM with
+    /// its own generator, which the seed doesn't affect.
+    pub(crate) fn rng(&self) -> ChaChaRng {
+        match self.seed {
+            Some(seed) => ChaChaRng::seed_from_u64(seed),
+            None => ChaChaRng::from_entropy(),
+        }
+    }
+
+    /// Returns `true` if written AVM files are read back to check them, unless `--no-verify-serialization` is given.
+    pub(crate) fn verifies_serialization(&self) -> bool {
+        !self.no_verify_serialization
+    }
+
+    /// Returns `true` if the compiled Aleo instructions can be reused when the source is unchanged.
+    /// This is not the case if printing the AST or any compiler snapshots are enabled. Whether dead code elimination
+    /// is enabled is recorded with the checksum instead, so that toggling it recompiles the program.
+    pub(crate) fn allows_incremental_build(&self) -> bool {
+        !(self.print_ast.is_some()
+            || self.enable_initial_symbol_table_snapshot
+            || self.enable_type_checked_symbol_table_snapshot
+            || self.enable_unrolled_symbol_table_snapshot
+            || self.enable_all_ast_snapshots
+            || self.enable_initial_input_ast_snapshot
+            || self.enable_initial_ast_snapshot
+            || self.enable_unrolled_ast_snapshot
+            || self.enable_ssa_ast_snapshot
+            || self.enable_flattened_ast_snapshot
+            || self.enable_destructured_ast_snapshot
+            || self.enable_inlined_ast_snapshot
+            || self.enable_dce_ast_snapshot)
+    }
+}
@@ -17,12 +17,20 @@
 use super::*;
 
 use snarkvm::{cli::New as SnarkVMNew, file::AleoFile};
+use std::str::FromStr;
 
 /// Create new Leo project
 #[derive(Parser, Debug)]
 pub struct New {
     #[clap(name = "NAME", help = "Set package name")]
     pub(crate) name: String,
+    #[clap(
+        long,
+        help = "Create the package from a starter template: hello, token, test or lib",
+        default_value = "hello",
+        value_parser = PackageTemplate::from_str
+    )]
+    pub(crate) template: PackageTemplate,
 }
 
 impl Command for New {
@@ -51,7 +59,7 @@ impl Command for New {
         package_path.push(&self.name);
 
         // Initialize the Leo package in the directory created by `aleo new`.
-        Package::<CurrentNetwork>::initialize(&self.name, &package_path)?;
+        Package::<CurrentNetwork>::initialize_with_template(&self.name, &package_path, self.template)?;
 
         // Change the cwd to the Leo package directory to compile aleo files.
         std::env::set_current_dir(&package_path)
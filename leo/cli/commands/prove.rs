@@ -0,0 +1,188 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::{
+    commands::build::input_file_inputs,
+    helpers::{offline::is_offline, progress::with_progress},
+};
+
+use leo_package::outputs::ProofFile;
+
+use snarkvm::{
+    circuit::AleoV0,
+    file::{ProverFile, VerifierFile},
+    ledger::{
+        query::{Query, QueryTrait},
+        store::{BlockStore, helpers::memory::BlockMemory},
+    },
+    package::Package,
+    prelude::{Execution, Identifier, Locator, PrivateKey, Response, Value},
+    synthesizer::program::{CallOperator, Instruction},
+};
+use std::str::FromStr;
+
+/// Prove the execution of a function of the current package with the synthesized proving keys.
+#[derive(Parser, Debug)]
+pub struct Prove {
+    #[clap(name = "NAME", help = "The name of the function to prove.", default_value = "main")]
+    name: String,
+
+    #[clap(name = "INPUTS", help = "The inputs to the function. If none are provided, the input file is used.")]
+    inputs: Vec<String>,
+
+    #[clap(
+        name = "ENDPOINT",
+        help = "The network endpoint queried for the current state root.",
+        default_value = "https://api.explorer.aleo.org/v1",
+        long
+    )]
+    endpoint: String,
+
+    #[clap(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Prove {
+    type Input = <Build as Command>::Output;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Prove")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        Build::new(self.compiler_options.clone()).execute(context)
+    }
+
+    fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
+        // If input values are provided, then prove the function with those inputs.
+        // Otherwise, use the input file.
+        let inputs = match self.inputs.is_empty() {
+            true => input_file_inputs(input, &self.name, &context.dir()?)?,
+            false => self.inputs,
+        };
+        let function_name = Identifier::<CurrentNetwork>::from_str(&self.name).map_err(CliError::failed_to_prove)?;
+        let inputs = inputs
+            .iter()
+            .map(|input| Value::<CurrentNetwork>::from_str(input))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(CliError::failed_to_prove)?;
+
+        // Open the Leo build/ directory, which holds the compiled Aleo package.
+        let path = context.dir()?;
+        let build_directory = BuildDirectory::open(&path)?;
+        let package = Package::<CurrentNetwork>::open(&build_directory).map_err(CliError::failed_to_prove)?;
+
+        // The proving keys must have been synthesized for the current program by `leo setup`.
+        if package.is_build_required::<AleoV0>() {
+            return Err(CliError::keys_out_of_date().into());
+        }
+
+        // Change the cwd to the Leo build/ directory, so the private key is loaded from the package `.env` file.
+        std::env::set_current_dir(&build_directory)
+            .map_err(|err| PackageError::failed_to_set_cwd(build_directory.display(), err))?;
+        let private_key = snarkvm::cli::helpers::dotenv_private_key().map_err(CliError::failed_to_prove)?;
+
+        tracing::info!("Proving the execution of `{function_name}`...");
+        // Offline, the execution is proven against the state root of an empty ledger.
+        let rng = &mut self.compiler_options.rng();
+        let (response, execution) = match is_offline() {
+            true => {
+                let block_store =
+                    BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).map_err(CliError::failed_to_prove)?;
+                with_progress("Proving", || {
+                    prove(&package, Query::from(block_store), &private_key, function_name, &inputs, rng)
+                })
+            }
+            false => with_progress("Proving", || {
+                prove(
+                    &package,
+                    Query::<_, BlockMemory<_>>::from(self.endpoint),
+                    &private_key,
+                    function_name,
+                    &inputs,
+                    rng,
+                )
+            }),
+        }
+        .map_err(CliError::failed_to_prove)?;
+        for output in response.outputs() {
+            tracing::info!("➡️  Output: {output}");
+        }
+
+        // The execution holds the proof along with the inputs and outputs of its transitions,
+        // so it can be verified without the original input file.
+        let outputs_directory = self.compiler_options.outputs_directory(&path)?;
+        OutputsDirectory::create_at(&outputs_directory)?;
+        let proof_file = ProofFile::new(&package.program_id().name().to_string());
+        let proof_path = outputs_directory.join(proof_file.file_name());
+        proof_file.write_to(&proof_path, execution.to_string())?;
+
+        tracing::info!("✅ Wrote the proof to '{}'", proof_path.display());
+
+        Ok(())
+    }
+}
+
+/// Proves the execution of the given function, with the state root provided by the given query.
+/// This mirrors `Package::execute`, which always queries the state root from a network endpoint.
+fn prove(
+    package: &Package<CurrentNetwork>,
+    query: impl QueryTrait<CurrentNetwork>,
+    private_key: &PrivateKey<CurrentNetwork>,
+    function_name: Identifier<CurrentNetwork>,
+    inputs: &[Value<CurrentNetwork>],
+    rng: &mut ChaChaRng,
+) -> snarkvm::console::network::prelude::Result<(Response<CurrentNetwork>, Execution<CurrentNetwork>)> {
+    let program_id = package.program_id();
+    let locator = Locator::<CurrentNetwork>::new(*program_id, function_name);
+
+    // Authorize the function call.
+    let process = package.get_process()?;
+    let authorization = process.authorize::<AleoV0, _>(private_key, program_id, function_name, inputs.iter(), rng)?;
+
+    // Load the keys of the functions called by the function, which `leo setup` wrote next to the program keys.
+    let program = process.get_program(program_id)?;
+    for instruction in program.get_function(&function_name)?.instructions() {
+        if let Instruction::Call(call) = instruction {
+            let (program, resource) = match call.operator() {
+                CallOperator::Locator(locator) => (process.get_program(locator.program_id())?, locator.resource()),
+                CallOperator::Resource(resource) => (program, resource),
+            };
+            if program.contains_function(resource) {
+                let directory =
+                    package.build_directory().join(format!("{}-{}", program.id().name(), program.id().network()));
+                let prover = ProverFile::open(&directory, resource)?;
+                process.insert_proving_key(program.id(), resource, prover.proving_key().clone())?;
+                let verifier = VerifierFile::open(&directory, resource)?;
+                process.insert_verifying_key(program.id(), resource, verifier.verifying_key().clone())?;
+            }
+        }
+    }
+
+    // Load the keys of the function itself.
+    let prover = ProverFile::open(&package.build_directory(), &function_name)?;
+    process.insert_proving_key(program_id, &function_name, prover.proving_key().clone())?;
+    let verifier = VerifierFile::open(&package.build_directory(), &function_name)?;
+    process.insert_verifying_key(program_id, &function_name, verifier.verifying_key().clone())?;
+
+    // Execute the function and prove its execution.
+    let (response, mut trace) = process.execute::<AleoV0, _>(authorization, rng)?;
+    trace.prepare(query)?;
+    let execution = trace.prove_execution::<AleoV0, _>(&locator.to_string(), rng)?;
+    Ok((response, execution))
+}
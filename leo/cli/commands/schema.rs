@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::commands::build::entry_file_path;
+
+use leo_ast::{Function, NodeBuilder, Type, Variant};
+use leo_package::{outputs::InputSchemaFile, root::ManifestConfig, source::MAIN_FILENAME};
+use leo_span::{source_map::FileName, symbol::with_session_globals};
+
+use serde_json::{Map, Value, json};
+use snarkvm::file::Manifest;
+
+/// Build the current package and write a JSON Schema of its input file, for editors to check and complete it with.
+#[derive(Parser, Debug)]
+pub struct Schema {
+    #[clap(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Schema {
+    type Input = <Build as Command>::Output;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        Build::new(self.compiler_options.clone()).execute(context)
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let package_path = context.dir()?;
+        let manifest =
+            Manifest::<CurrentNetwork>::open(&package_path).map_err(PackageError::failed_to_open_manifest)?;
+        let program_name = manifest.program_id().name().to_string();
+
+        // The parameters are read from the Leo source, as the compiled program only names them by register.
+        let config = ManifestConfig::read_from(&package_path)?;
+        let source_directory = self.compiler_options.source_directory(&package_path, &config.build)?;
+        let source_file_path = match &self.compiler_options.entry {
+            Some(entry) => entry_file_path(entry, &source_directory)?,
+            None => source_directory.join(MAIN_FILENAME),
+        };
+        let source = std::fs::read_to_string(&source_file_path)
+            .map_err(|e| CompilerError::file_read_error(&source_file_path, e))?;
+        let sf = with_session_globals(|s| s.source_map.new_source(&source, FileName::Real(source_file_path.clone())));
+        let ast = leo_parser::parse_ast(&Handler::default(), &NodeBuilder::default(), &sf.src, sf.start_pos)?;
+
+        let mut sections = Map::new();
+        for scope in ast.as_repr().program_scopes.values() {
+            for (name, function) in scope.functions.iter() {
+                // The sections of an input file hold the inputs of the transitions, which are the only entry points.
+                if matches!(function.variant, Variant::Transition) {
+                    sections.insert(name.to_string(), section_schema(function));
+                }
+            }
+        }
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": format!("The input file of {}", manifest.program_id()),
+            "type": "object",
+            "properties": sections,
+            "additionalProperties": false,
+        });
+
+        let outputs_directory = self.compiler_options.outputs_directory(&package_path)?;
+        let input_schema_file = InputSchemaFile::new(&program_name);
+        let input_schema_path = outputs_directory.join(input_schema_file.file_name());
+        let schema = serde_json::to_string_pretty(&schema).expect("the schema is serializable");
+        input_schema_file.write_to(&input_schema_path, schema)?;
+        tracing::info!("✅ Wrote the input schema to '{}'", input_schema_path.display());
+
+        Ok(())
+    }
+}
+
+/// Returns the schema of the section of the input file holding the inputs of the given function.
+fn section_schema(function: &Function) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for input in function.input.iter() {
+        let name = input.identifier().to_string();
+        let mode = match input.mode().to_string() {
+            mode if mode.is_empty() => "private".to_string(),
+            mode => mode,
+        };
+        let mut property = type_schema(&input.type_());
+        property.insert("description".to_string(), json!(format!("The {mode} input `{name}: {}`.", input.type_())));
+        properties.insert(name.clone(), Value::Object(property));
+        required.push(name);
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Returns the schema of a value of the given type, as it is written in an input file.
+/// Only the types with a literal syntax are checked; the other values are only described.
+fn type_schema(type_: &Type) -> Map<String, Value> {
+    let literal = |suffix: &str, signed: bool| {
+        let sign = if signed { "-?" } else { "" };
+        json!({ "type": "string", "pattern": format!("^{sign}[0-9][0-9_]*{suffix}$") })
+    };
+    let schema = match type_ {
+        Type::Address => json!({ "type": "string", "pattern": "^aleo1[a-z0-9]{58}$" }),
+        Type::Boolean => json!({ "type": "boolean" }),
+        Type::Field => literal("field", true),
+        Type::Group => literal("group", true),
+        Type::Integer(integer_type) => literal(&integer_type.to_string(), integer_type.is_signed()),
+        Type::Scalar => literal("scalar", false),
+        _ => json!({}),
+    };
+    match schema {
+        Value::Object(schema) => schema,
+        _ => unreachable!("the schema of a type is an object"),
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::helpers::progress::with_progress;
+
+use leo_package::{
+    imports::IMPORTS_DIRECTORY_NAME,
+    outputs::{ChecksumAlgorithm, ChecksumFile, KEYS_CHECKSUM_FILE_EXTENSION, MAIN_ALEO_FILE_NAME},
+    root::ManifestConfig,
+};
+
+use snarkvm::{circuit::AleoV0, package::Package};
+
+/// Build the current package and synthesize the proving and verifying keys of its functions.
+#[derive(Parser, Debug)]
+pub struct Setup {
+    #[clap(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+    #[clap(long, help = "Synthesizes the keys even if they are up to date.")]
+    pub(crate) force: bool,
+}
+
+impl Command for Setup {
+    type Input = <Build as Command>::Output;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Setup")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        Build::new(self.compiler_options.clone()).execute(context)
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Open the Leo build/ directory, which holds the compiled Aleo package.
+        let path = context.dir()?;
+        let build_directory = BuildDirectory::open(&path)?;
+        let package = Package::<CurrentNetwork>::open(&build_directory).map_err(CliError::failed_to_synthesize_keys)?;
+        let keys_directory = package.build_directory();
+
+        // The keys are only synthesized again if the compiled programs changed since the last setup, as recorded by
+        // their checksum in the outputs directory, or if the key files of a function are missing.
+        let program_name = package.program_id().name().to_string();
+        let checksum_file =
+            ChecksumFile::new(&program_name).with_file_name(&format!("{program_name}{KEYS_CHECKSUM_FILE_EXTENSION}"));
+        let checksum_path = self.compiler_options.outputs_directory(&path)?.join(checksum_file.file_name());
+        let checksum =
+            compiled_programs_checksum(&build_directory, ManifestConfig::read_from(&path)?.build.checksum_algorithm)?;
+        let keys_exist = package.program().functions().keys().all(|function_name| {
+            keys_directory.join(format!("{function_name}.prover")).exists()
+                && keys_directory.join(format!("{function_name}.verifier")).exists()
+        });
+        let up_to_date = keys_exist
+            && checksum_file.read_from(&checksum_path).is_ok_and(|stored_checksum| stored_checksum == checksum);
+        if up_to_date && !self.force {
+            tracing::info!("✅ The proving and verifying keys are up to date.");
+            return Ok(());
+        }
+
+        tracing::info!("Synthesizing the proving and verifying keys...");
+        with_progress("Synthesizing the keys", || package.build::<AleoV0>(None))
+            .map_err(CliError::failed_to_synthesize_keys)?;
+        checksum_file.write_to(&checksum_path, checksum)?;
+
+        // Log the key files of each function of the program.
+        for function_name in package.program().functions().keys() {
+            tracing::info!(
+                "✅ Wrote the keys of `{function_name}` to '{}' and '{}'",
+                keys_directory.join(format!("{function_name}.prover")).display(),
+                keys_directory.join(format!("{function_name}.verifier")).display()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the checksum of the compiled program and of its compiled imports, which the keys are synthesized from.
+fn compiled_programs_checksum(build_directory: &Path, checksum_algorithm: ChecksumAlgorithm) -> Result<String> {
+    let read = |path: &Path| std::fs::read(path).map_err(|e| PackageError::failed_to_read_file(path.display(), e));
+    let program = read(&build_directory.join(MAIN_ALEO_FILE_NAME))?;
+
+    let mut imports = Vec::new();
+    let imports_directory = build_directory.join(IMPORTS_DIRECTORY_NAME);
+    if imports_directory.is_dir() {
+        let entries = std::fs::read_dir(&imports_directory)
+            .map_err(|e| PackageError::failed_to_read_directory(imports_directory.display(), e))?;
+        for entry in entries {
+            let path =
+                entry.map_err(|e| PackageError::failed_to_read_directory(imports_directory.display(), e))?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            imports.push((name, read(&path)?));
+        }
+    }
+    // The entries of a directory are in no particular order.
+    imports.sort();
+
+    Ok(checksum_algorithm.combined_checksum(&checksum_algorithm.checksum(&program), &imports))
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::{
+    circuit::AleoV0,
+    package::Package,
+    prelude::{Identifier, Value},
+};
+use std::{panic::AssertUnwindSafe, str::FromStr};
+
+/// Run the transitions of the current package annotated with `@test`.
+#[derive(Parser, Debug)]
+pub struct Test {
+    #[clap(name = "FILTER", help = "Only run the tests whose names contain this string.")]
+    filter: Option<String>,
+
+    #[clap(long, help = "Stop at the first failing test.")]
+    fail_fast: bool,
+
+    #[clap(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Test {
+    type Input = <Build as Command>::Output;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Test")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        Build::new(self.compiler_options.clone()).execute(context)
+    }
+
+    fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
+        let BuildOutput { input_ast, structs, tests, .. } = input;
+        let (selected, filtered): (Vec<_>, Vec<_>) =
+            tests.into_iter().partition(|name| self.filter.iter().all(|filter| name.contains(filter.as_str())));
+
+        // Open the Leo build/ directory, which holds the compiled Aleo package.
+        let path = context.dir()?;
+        let build_directory = BuildDirectory::open(&path)?;
+        let package = Package::<CurrentNetwork>::open(&build_directory).map_err(CliError::failed_to_execute_run)?;
+
+        // Change the cwd to the Leo build/ directory, so the private key is loaded from the package `.env` file.
+        std::env::set_current_dir(&build_directory)
+            .map_err(|err| PackageError::failed_to_set_cwd(build_directory.display(), err))?;
+        let private_key = snarkvm::cli::helpers::dotenv_private_key().map_err(CliError::failed_to_execute_run)?;
+
+        println!();
+        println!("running {} test{}", selected.len(), if selected.len() == 1 { "" } else { "s" });
+
+        // Panics are reported as test failures, so the Leo panic hook is silenced while the tests run.
+        let panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+
+        let rng = &mut self.compiler_options.rng();
+        let (mut passed, mut failed) = (0, 0);
+        for name in &selected {
+            // A test takes its inputs from the section of the input file named after it, if any.
+            let inputs = match &input_ast {
+                Some(input_ast) => input_ast.program_inputs(name, structs.clone()),
+                None => Vec::new(),
+            };
+            // An assertion on constants fails by panicking during synthesis, so a panic fails the test too.
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                let function_name = Identifier::<CurrentNetwork>::from_str(name)?;
+                let inputs = inputs
+                    .iter()
+                    .map(|input| Value::<CurrentNetwork>::from_str(input))
+                    .collect::<Result<Vec<_>, _>>()?;
+                package.run::<AleoV0, _>(&private_key, function_name, &inputs, rng)
+            }))
+            .unwrap_or_else(|panic| {
+                let message = match panic.downcast_ref::<String>() {
+                    Some(message) => message.clone(),
+                    None => panic.downcast_ref::<&str>().map_or("the test panicked".to_string(), |m| m.to_string()),
+                };
+                Err(snarkvm::prelude::Error::msg(message))
+            });
+            match result {
+                Ok((_, metrics)) => {
+                    let constraints: u64 = metrics
+                        .iter()
+                        .map(|call| {
+                            call.num_request_constraints + call.num_function_constraints + call.num_response_constraints
+                        })
+                        .sum();
+                    println!("test {name} ... ok ({constraints} constraints)");
+                    passed += 1;
+                }
+                Err(error) => {
+                    println!("test {name} ... FAILED");
+                    println!("    {error}");
+                    failed += 1;
+                    if self.fail_fast {
+                        break;
+                    }
+                }
+            }
+        }
+
+        std::panic::set_hook(panic_hook);
+
+        println!();
+        println!(
+            "test result: {}. {passed} passed; {failed} failed; {} filtered out",
+            if failed == 0 { "ok" } else { "FAILED" },
+            filtered.len()
+        );
+
+        match failed {
+            0 => Ok(()),
+            failed => Err(CliError::tests_failed(failed).into()),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::cli::commands::{
+    build::COMPILER_VERSION,
+    bundle::{BUNDLE_FORMAT_VERSION, BUNDLE_METADATA_FILE_NAME, BundleMetadata, checksum_algorithm},
+};
+
+use leo_package::{build::BUILD_DIRECTORY_NAME, outputs::MAIN_ALEO_FILE_NAME, write_atomically};
+use snarkvm::prelude::Program;
+
+use std::{collections::BTreeMap, io::Read, str::FromStr};
+
+/// Extract a bundle written by `leo bundle`, checking that its files match their checksums.
+#[derive(Parser, Debug)]
+pub struct Unbundle {
+    #[clap(name = "BUNDLE", help = "The bundle to extract")]
+    pub(crate) bundle: PathBuf,
+    #[clap(
+        long,
+        value_name = "DIRECTORY",
+        help = "Extracts the bundle into the given directory instead of the current package."
+    )]
+    pub(crate) output_dir: Option<PathBuf>,
+}
+
+impl Command for Unbundle {
+    type Input = ();
+    type Output = BundleMetadata;
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let invalid = |reason: &dyn std::fmt::Display| CliError::invalid_bundle(self.bundle.display(), reason);
+        let bundle = std::fs::File::open(&self.bundle).map_err(|e| invalid(&e))?;
+        let mut archive = zip::ZipArchive::new(bundle).map_err(|e| invalid(&e))?;
+
+        // A file is only extracted inside the directory, whatever its name in the bundle.
+        // Directories are created along with their files, so their entries are skipped.
+        let mut files = BTreeMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).map_err(|e| invalid(&e))?;
+            if file.is_dir() {
+                continue;
+            }
+            if file.enclosed_name().is_none() {
+                return Err(invalid(&format!("`{}` is outside of the package", file.name())).into());
+            }
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents).map_err(|e| invalid(&e))?;
+            files.insert(file.name().to_string(), contents);
+        }
+
+        let metadata = files.remove(BUNDLE_METADATA_FILE_NAME).ok_or_else(|| invalid(&"it has no `bundle.json`"))?;
+        let metadata: BundleMetadata = serde_json::from_slice(&metadata).map_err(|e| invalid(&e))?;
+        if metadata.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(invalid(&format!("its format version {} is not supported", metadata.format_version)).into());
+        }
+
+        // Every file must be listed with its checksum, and every listed file must be in the bundle.
+        if let Some(name) = files.keys().find(|name| !metadata.files.contains_key(*name)) {
+            return Err(invalid(&format!("`{name}` is not listed in `bundle.json`")).into());
+        }
+        for (name, checksum) in &metadata.files {
+            let contents = files.get(name).ok_or_else(|| invalid(&format!("`{name}` is missing")))?;
+            let algorithm = checksum_algorithm(checksum)
+                .ok_or_else(|| invalid(&format!("the checksum `{checksum}` of `{name}` has an unknown algorithm")))?;
+            let actual = algorithm.checksum(contents);
+            if &actual != checksum {
+                return Err(invalid(&format!("the checksum of `{name}` is `{actual}`, not `{checksum}`")).into());
+            }
+        }
+        let main = format!("{BUILD_DIRECTORY_NAME}{MAIN_ALEO_FILE_NAME}");
+        let program = files.get(&main).ok_or_else(|| invalid(&format!("`{main}` is missing")))?;
+        Program::<CurrentNetwork>::from_str(&String::from_utf8_lossy(program))
+            .map_err(|e| invalid(&format!("`{main}` is not a well-formed program: {e}")))?;
+
+        if metadata.compiler_version != COMPILER_VERSION {
+            tracing::warn!("The bundle was built by Leo {}, not Leo {COMPILER_VERSION}", metadata.compiler_version);
+        }
+
+        // The files are only written once the whole bundle is checked.
+        let directory = match self.output_dir {
+            Some(directory) => directory,
+            None => context.dir()?,
+        };
+        for (name, contents) in &files {
+            let path = directory.join(name);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(CliError::failed_to_write_file)?;
+            }
+            write_atomically(&path, contents).map_err(CliError::failed_to_write_file)?;
+        }
+
+        tracing::info!(
+            "✅ Extracted {} files of '{}', built by Leo {}, into '{}'",
+            files.len(),
+            metadata.package_name,
+            metadata.compiler_version,
+            directory.display()
+        );
+        Ok(metadata)
+    }
+}
@@ -15,7 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::cli::helpers::updater::Updater;
+use crate::cli::helpers::{offline::ensure_online, updater::Updater};
 
 /// Update Leo to the latest version
 #[derive(Debug, Parser)]
@@ -44,6 +44,7 @@ impl Command for Update {
     where
         Self: Sized,
     {
+        ensure_online("the releases of Leo")?;
         match self.list {
             true => match Updater::show_available_releases() {
                 Ok(output) => tracing::info!("{output}"),
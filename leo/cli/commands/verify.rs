@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use leo_package::outputs::ProofFile;
+
+use snarkvm::{
+    file::VerifierFile,
+    package::Package,
+    prelude::{Execution, FromBytes},
+};
+use std::{str::FromStr, time::Instant};
+
+/// Verify a proof written by `leo prove` against the verifying keys of the current package.
+#[derive(Parser, Debug)]
+pub struct Verify {
+    #[clap(long, help = "Verifies the proof at the given path instead of the one in the outputs directory.")]
+    proof: Option<PathBuf>,
+
+    #[clap(long, help = "Verifies the proven function with the verifying key at the given path.")]
+    vk: Option<PathBuf>,
+
+    #[clap(long, help = "Reads the proof from the given directory instead of `outputs/`.")]
+    output_dir: Option<PathBuf>,
+}
+
+impl Command for Verify {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Verify")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Open the Leo build/ directory, which holds the compiled Aleo package and its keys.
+        let path = context.dir()?;
+        let build_directory = BuildDirectory::open(&path)?;
+        let package = Package::<CurrentNetwork>::open(&build_directory).map_err(CliError::failed_to_verify)?;
+        let process = package.get_process().map_err(CliError::failed_to_verify)?;
+
+        // Read the proven execution.
+        let proof_file = ProofFile::new(&package.program_id().name().to_string());
+        let proof_path = match self.proof {
+            Some(proof) => proof,
+            None => match self.output_dir {
+                Some(output_dir) => std::env::current_dir().map_err(CliError::cli_io_error)?.join(output_dir),
+                None => path.join(OUTPUTS_DIRECTORY_NAME),
+            }
+            .join(proof_file.file_name()),
+        };
+        let execution = Execution::<CurrentNetwork>::from_str(&proof_file.read_from(&proof_path)?)
+            .map_err(CliError::failed_to_verify)?;
+
+        // The verifying key given with `--vk` takes precedence over the one written by `leo setup`.
+        if let Some(vk) = self.vk {
+            let bytes =
+                std::fs::read(&vk).map_err(|err| CliError::failed_to_verify(format!("{}: {err}", vk.display())))?;
+            let verifier = VerifierFile::<CurrentNetwork>::from_bytes_le(&bytes).map_err(CliError::failed_to_verify)?;
+            process
+                .insert_verifying_key(package.program_id(), verifier.function_name(), verifier.verifying_key().clone())
+                .map_err(CliError::failed_to_verify)?;
+        }
+
+        // Load the verifying key of every proven transition, which are written next to the keys of their program.
+        for transition in execution.transitions() {
+            let (program_id, function_name) = (transition.program_id(), transition.function_name());
+            if process.get_stack(program_id).and_then(|stack| stack.get_verifying_key(function_name)).is_ok() {
+                continue;
+            }
+            let directory = match program_id == package.program_id() {
+                true => package.build_directory(),
+                false => package.build_directory().join(format!("{}-{}", program_id.name(), program_id.network())),
+            };
+            let verifier = VerifierFile::open(&directory, function_name).map_err(CliError::failed_to_verify)?;
+            process
+                .insert_verifying_key(program_id, function_name, verifier.verifying_key().clone())
+                .map_err(CliError::failed_to_verify)?;
+        }
+
+        // Verify the proof against the public inputs and outputs recorded in the execution.
+        let start = Instant::now();
+        let result = process.verify_execution(&execution);
+        let elapsed = start.elapsed().as_millis();
+        match result {
+            Ok(()) => {
+                tracing::info!("✅ PASS '{}' ({elapsed} ms)", proof_path.display());
+                Ok(())
+            }
+            Err(err) => {
+                tracing::info!("❌ FAIL '{}' ({elapsed} ms)", proof_path.display());
+                Err(CliError::proof_verification_failed(err).into())
+            }
+        }
+    }
+}
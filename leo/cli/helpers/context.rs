@@ -51,6 +51,11 @@ impl Context {
     /// Returns the package name as a String.
     /// Opens the manifest file `program.json` and creates the build directory if it doesn't exist.
     pub fn open_manifest(&self) -> Result<Manifest<CurrentNetwork>> {
+        self.open_manifest_into(&self.dir()?)
+    }
+
+    /// Opens the manifest file `program.json`, mirroring it into the build directory in the given directory.
+    pub fn open_manifest_into(&self, build_root: &Path) -> Result<Manifest<CurrentNetwork>> {
         // Open the manifest file.
         let path = self.dir()?;
         let manifest = Manifest::<CurrentNetwork>::open(&path).map_err(PackageError::failed_to_open_manifest)?;
@@ -59,7 +64,7 @@ impl Context {
         // let program_id = manifest.program_id();
 
         // Create the Leo build/ directory if it doesn't exist.
-        let build_path = path.join(Path::new(BUILD_DIRECTORY_NAME));
+        let build_path = build_root.join(Path::new(BUILD_DIRECTORY_NAME));
         if !build_path.exists() {
             BuildDirectory::create(&build_path)?;
         }
@@ -17,9 +17,24 @@
 use leo_errors::Result;
 
 use colored::Colorize;
-use std::{fmt, sync::Once};
-use tracing::{event::Event, subscriber::Subscriber};
+use serde_json::{Map, Value};
+use std::{
+    fmt,
+    io::IsTerminal,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex,
+        Once,
+    },
+};
+use tracing::{
+    event::Event,
+    field::{Field, Visit},
+    subscriber::Subscriber,
+};
 use tracing_subscriber::{
+    field::RecordFields,
     fmt::{format::*, time::*, FmtContext, FormattedFields},
     registry::LookupSpan,
     FmtSubscriber,
@@ -27,6 +42,17 @@ use tracing_subscriber::{
 
 static START: Once = Once::new();
 
+/// Whether all logs are written to stderr, as they are while a command writes its output to stdout.
+static LOGS_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Writes all logs to stderr, or only the warnings and errors, for the rest of the process.
+pub fn set_logs_to_stderr(to_stderr: bool) {
+    LOGS_TO_STDERR.store(to_stderr, Ordering::Relaxed);
+}
+
+/// The number of spaces by which the events of a span are indented below its header.
+const INDENT: usize = 2;
+
 #[derive(Debug, Clone)]
 pub struct Format<F = Full, T = SystemTime> {
     format: F,
@@ -37,6 +63,8 @@ pub struct Format<F = Full, T = SystemTime> {
     pub display_level: bool,
     pub display_thread_id: bool,
     pub display_thread_name: bool,
+    /// The headers of the spans of the last event, from the outermost to the innermost.
+    headers: Arc<Mutex<Vec<String>>>,
 }
 
 impl<F, T> Format<F, T> {
@@ -66,6 +94,7 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            headers: self.headers,
         }
     }
 
@@ -89,6 +118,7 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            headers: self.headers,
         }
     }
 
@@ -102,6 +132,7 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            headers: self.headers,
         }
     }
 
@@ -147,6 +178,7 @@ impl Default for Format<Full, SystemTime> {
             display_level: true,
             display_thread_id: false,
             display_thread_name: false,
+            headers: Arc::default(),
         }
     }
 }
@@ -160,38 +192,39 @@ where
         let meta = event.metadata();
 
         if self.display_level {
-            fn colored_string(level: &tracing::Level, message: &str) -> colored::ColoredString {
-                match *level {
-                    tracing::Level::ERROR => message.bold().red(),
-                    tracing::Level::WARN => message.bold().yellow(),
-                    tracing::Level::INFO => message.bold().cyan(),
-                    tracing::Level::DEBUG => message.bold().magenta(),
-                    tracing::Level::TRACE => message.bold(),
-                }
-            }
-
-            let mut message = "".to_string();
-
-            match context.lookup_current() {
-                Some(span_ref) => {
-                    let scope = span_ref.scope();
-
-                    for span in scope {
-                        message += span.metadata().name();
-
+            // Events are grouped under a header for each span they are in, like the phases of a cargo build.
+            // A header is only printed when an event is logged in a span other than that of the previous event.
+            let scope = match context.lookup_current() {
+                Some(span_ref) => span_ref
+                    .scope()
+                    .from_root()
+                    .map(|span| {
                         let ext = span.extensions();
                         let fields = &ext
                             .get::<FormattedFields<N>>()
                             .expect("Unable to find FormattedFields in extensions; this is a bug");
-                        if !fields.is_empty() {
-                            message = format!("{message} {{{fields}}}");
+                        match fields.is_empty() {
+                            true => span.metadata().name().to_string(),
+                            false => format!("{} {{{fields}}}", span.metadata().name()),
                         }
-                    }
-                }
-                None => return Err(std::fmt::Error),
+                    })
+                    .collect::<Vec<_>>(),
+                None => Vec::new(),
+            };
+            let mut headers = self.headers.lock().map_err(|_| fmt::Error)?;
+            let unchanged = headers.iter().zip(&scope).take_while(|(printed, header)| printed == header).count();
+            for (depth, header) in scope.iter().enumerate().skip(unchanged) {
+                writeln!(&mut writer, "{:indent$}{}", "", header.bold().green(), indent = depth * INDENT)?;
             }
+            *headers = scope.clone();
 
-            write!(&mut writer, "{:>10} ", colored_string(meta.level(), &message)).expect("Error writing event");
+            // Events are indented below the header of the innermost span, and warnings and errors are labelled.
+            write!(&mut writer, "{:indent$}", "", indent = scope.len() * INDENT)?;
+            match *meta.level() {
+                tracing::Level::ERROR => write!(&mut writer, "{} ", "error:".bold().red())?,
+                tracing::Level::WARN => write!(&mut writer, "{} ", "warning:".bold().yellow())?,
+                _ => {}
+            }
         }
 
         context.format_fields(writer.by_ref(), event)?;
@@ -199,31 +232,162 @@ where
     }
 }
 
+/// The format in which the CLI logs its output.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Events grouped under a header for each of their spans.
+    #[default]
+    Human,
+    /// One JSON object per event, for log aggregators.
+    Json,
+}
+
+/// Records the fields of an event or a span as a JSON object.
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+/// Formats the fields of spans as JSON objects, so that [`JsonFormat`] can nest them in the events.
+pub struct JsonFields;
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = JsonVisitor::default();
+        fields.record(&mut visitor);
+        write!(writer, "{}", Value::Object(visitor.0))
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &tracing::span::Record<'_>,
+    ) -> fmt::Result {
+        // The fields recorded after the span was created are merged into the object of its initial fields.
+        let mut visitor = JsonVisitor(serde_json::from_str(&current.fields).unwrap_or_default());
+        fields.record(&mut visitor);
+        current.fields = Value::Object(visitor.0).to_string();
+        Ok(())
+    }
+}
+
+/// Formats each event as a JSON object on its own line, with its level, target, spans, fields, and timestamp.
+pub struct JsonFormat;
+
+impl<S> FormatEvent<S, JsonFields> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        context: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        // The spans of the event from the outermost to the innermost, each with its name and fields.
+        let spans = match context.lookup_current() {
+            Some(span_ref) => span_ref
+                .scope()
+                .from_root()
+                .map(|span| {
+                    let ext = span.extensions();
+                    let mut object = ext
+                        .get::<FormattedFields<JsonFields>>()
+                        .and_then(|fields| serde_json::from_str::<Map<String, Value>>(&fields.fields).ok())
+                        .unwrap_or_default();
+                    object.insert("name".to_string(), span.metadata().name().into());
+                    Value::Object(object)
+                })
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+
+        let mut fields = JsonVisitor::default();
+        event.record(&mut fields);
+
+        let mut object = Map::new();
+        object.insert("timestamp".to_string(), timestamp.into());
+        object.insert("level".to_string(), meta.level().as_str().into());
+        object.insert("target".to_string(), meta.target().into());
+        if let Some(span) = spans.last() {
+            object.insert("span".to_string(), span.clone());
+        }
+        object.insert("spans".to_string(), spans.into());
+        object.insert("fields".to_string(), Value::Object(fields.0));
+        writeln!(writer, "{}", Value::Object(object))
+    }
+}
+
 /// Initialize logger with custom format and verbosity.
-pub fn init_logger(_app_name: &'static str, verbosity: usize) -> Result<()> {
+/// A verbosity of 0 only prints errors, 1 adds warnings and information, 2 debug and 3 trace output.
+pub fn init_logger(_app_name: &'static str, verbosity: usize, format: LogFormat) -> Result<()> {
     // This line enables Windows 10 ANSI coloring API.
     #[cfg(target_family = "windows")]
     ansi_term::enable_ansi_support().map_err(|_| leo_errors::CliError::failed_to_enable_ansi_support())?;
 
     use tracing_subscriber::fmt::writer::MakeWriterExt;
 
+    // Colors are disabled if `NO_COLOR` is set, or if the output is not a terminal.
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    // JSON logs are never colored, as the escape codes would end up in the messages.
+    if no_color || format == LogFormat::Json || !std::io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
+
     let stderr = std::io::stderr.with_max_level(tracing::Level::WARN);
-    let mk_writer = stderr.or_else(std::io::stdout);
+    let stdout = || -> Box<dyn std::io::Write> {
+        match LOGS_TO_STDERR.load(Ordering::Relaxed) {
+            true => Box::new(std::io::stderr()),
+            false => Box::new(std::io::stdout()),
+        }
+    };
+    let mk_writer = stderr.or_else(stdout);
 
-    let subscriber = FmtSubscriber::builder()
+    let builder = FmtSubscriber::builder()
         // all spans/events with a level higher than TRACE (e.g, debug, info, warn, etc.)
         // will be written to stdout.
         .with_max_level(match verbosity {
-            0 => tracing::Level::WARN,
+            0 => tracing::Level::ERROR,
             1 => tracing::Level::INFO,
             2 => tracing::Level::DEBUG,
             _ => tracing::Level::TRACE
         })
         .with_writer(mk_writer)
         .without_time()
-        .with_target(false)
-        .event_format(Format::default())
-        .finish();
+        .with_target(false);
+    let subscriber: Box<dyn Subscriber + Send + Sync> = match format {
+        LogFormat::Human => Box::new(builder.event_format(Format::default()).finish()),
+        LogFormat::Json => Box::new(builder.fmt_fields(JsonFields).event_format(JsonFormat).finish()),
+    };
 
     // call this line only once per process. needed for tests using same thread
     START.call_once(|| {
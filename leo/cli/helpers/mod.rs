@@ -18,4 +18,7 @@ pub use super::*;
 
 pub mod context;
 pub mod logger;
+pub mod offline;
+pub mod progress;
+pub mod timeout;
 pub mod updater;
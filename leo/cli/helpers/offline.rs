@@ -0,0 +1,41 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{CliError, Result};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether network access is forbidden, as it is by the global `--offline` flag or `LEO_OFFLINE`.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Forbids or allows network access for the rest of the process.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Returns `true` if network access is forbidden.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Returns an error if network access is forbidden. Every code path fetching from the network, such as the
+/// resolution of remote imports, must call this first with a description of what it fetches.
+pub fn ensure_online(fetch: &str) -> Result<()> {
+    match is_offline() {
+        true => Err(CliError::offline_forbidden(fetch).into()),
+        false => Ok(()),
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    io::{IsTerminal, Write},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+/// The interval at which the elapsed time is updated on a terminal.
+const TERMINAL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The interval at which a line is logged if the output is not a terminal.
+const LOG_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Runs the given work, showing that it is still running until it returns.
+/// On a terminal, the elapsed time is updated in place. Otherwise, a line is logged periodically.
+/// Nothing is shown if the output is suppressed with `--quiet`.
+pub fn with_progress<T>(message: &str, work: impl FnOnce() -> T) -> T {
+    if !tracing::enabled!(tracing::Level::INFO) {
+        return work();
+    }
+
+    let is_terminal = std::io::stderr().is_terminal();
+    let interval = match is_terminal {
+        true => TERMINAL_INTERVAL,
+        false => LOG_INTERVAL,
+    };
+    let (done, finished) = mpsc::channel::<()>();
+
+    std::thread::scope(|scope| {
+        // The reporting thread sleeps between updates, so it doesn't compete with the work for the CPU.
+        scope.spawn(move || {
+            let start = Instant::now();
+            while let Err(mpsc::RecvTimeoutError::Timeout) = finished.recv_timeout(interval) {
+                let elapsed = start.elapsed().as_secs();
+                match is_terminal {
+                    true => {
+                        eprint!("\r{message} ({elapsed} s)");
+                        let _ = std::io::stderr().flush();
+                    }
+                    false => tracing::info!("{message} ({elapsed} s)"),
+                }
+            }
+            // Clear the line of the elapsed time.
+            if is_terminal && start.elapsed() >= interval {
+                eprint!("\r\x1b[2K");
+            }
+        });
+
+        let output = work();
+        drop(done);
+        output
+    })
+}
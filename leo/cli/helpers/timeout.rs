@@ -0,0 +1,87 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{CliError, LeoError, Result};
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc,
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Fails the build if it takes longer than the given time limit.
+/// The limit is checked at each step of the build recorded with [`Timeout::set_status`], and while the work run by
+/// [`Timeout::run`] is running, which then returns [`CliError::build_timed_out`] reporting the step that ran over and
+/// the constraints synthesized so far, so that the build stops without exiting the process.
+/// Files are written atomically, so the artifacts of a previous build are left intact.
+pub struct Timeout {
+    seconds: u64,
+    deadline: Instant,
+    status: Mutex<String>,
+    constraints: AtomicU64,
+}
+
+impl Timeout {
+    /// Starts the time limit of `seconds`.
+    pub fn start(seconds: u64) -> Self {
+        let deadline = Instant::now() + Duration::from_secs(seconds);
+        Self { seconds, deadline, status: Mutex::new("starting the build".to_string()), constraints: AtomicU64::new(0) }
+    }
+
+    /// Records how far the work got, e.g. `compiling 'main.leo'`, or fails if the time limit has passed.
+    pub fn set_status(&self, status: impl Into<String>) -> Result<()> {
+        let mut current = self.status.lock().unwrap_or_else(|error| error.into_inner());
+        if Instant::now() > self.deadline {
+            return Err(self.timed_out(&current));
+        }
+        *current = status.into();
+        Ok(())
+    }
+
+    /// Records the constraints of a synthesized circuit, which are reported if the build times out.
+    pub fn add_constraints(&self, constraints: u64) {
+        self.constraints.fetch_add(constraints, Ordering::Relaxed);
+    }
+
+    /// Runs `work` on a worker thread, and fails once the time limit has passed even if it is still running, e.g. in
+    /// a synthesis by snarkVM which never returns. The worker is then left to finish in the background, and as the
+    /// caller no longer waits for its output, nothing is written from it.
+    pub fn run<T: Send + 'static>(&self, work: impl FnOnce() -> T + Send + 'static) -> Result<T> {
+        let (sender, receiver) = mpsc::channel();
+        let worker = std::thread::spawn(move || {
+            // The receiver is gone if the build timed out.
+            let _ = sender.send(work());
+        });
+        match receiver.recv_timeout(self.deadline.saturating_duration_since(Instant::now())) {
+            Ok(output) => Ok(output),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                Err(self.timed_out(&self.status.lock().unwrap_or_else(|error| error.into_inner())))
+            }
+            // The worker panicked before sending its output, so the panic is passed on.
+            Err(mpsc::RecvTimeoutError::Disconnected) => match worker.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("the worker sends its output before it returns"),
+            },
+        }
+    }
+
+    fn timed_out(&self, status: &str) -> LeoError {
+        CliError::build_timed_out(self.seconds, status, self.constraints.load(Ordering::Relaxed)).into()
+    }
+}
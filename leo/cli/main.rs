@@ -17,8 +17,6 @@
 use leo_lang::cli::*;
 use leo_span::symbol::create_session_if_not_set_then;
 
-use clap::Parser;
-
 fn set_panic_hook() {
     #[cfg(not(debug_assertions))]
     std::panic::set_hook({
@@ -38,12 +36,12 @@ fn set_panic_hook() {
                 sys_info::os_release().unwrap_or_else(|e| e.to_string()),
             );
             eprintln!("note: compiler args: {}\n", std::env::args().collect::<Vec<_>>().join(" "));
-            eprintln!("note: compiler flags: {:?}\n", CLI::parse());
+            eprintln!("note: compiler flags: {:?}\n", CLI::parse_with_env());
         })
     });
 }
 
 fn main() {
     set_panic_hook();
-    create_session_if_not_set_then(|_| handle_error(run_with_args(CLI::parse())));
+    create_session_if_not_set_then(|_| handle_error(run_with_args(CLI::parse_with_env())));
 }
@@ -14,7 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::cli::BuildOptions;
 use leo_errors::Result;
+use tempfile::TempDir;
 // use std::path::PathBuf;
 
 /* use crate::{
@@ -27,23 +29,550 @@ use leo_errors::Result;
     context::{create_context, Context},
 }; */
 
+/// The source of a program returning its input, built by most of the tests.
+const IDENTITY_PROGRAM: &str =
+    "program foo.aleo {\n    transition main(a: u32) -> u32 {\n        return a;\n    }\n}\n";
+
+/// Returns a temporary package of the program `{name}.aleo`, with the given source as its `src/main.leo`.
+/// The package is removed when the returned directory is dropped, including when the test fails.
+fn temp_package(name: &str, source: &str) -> TempDir {
+    let directory = tempfile::Builder::new().prefix("leo-package-").tempdir().unwrap();
+    write_package(directory.path(), name, source);
+    directory
+}
+
+/// Writes the package of the program `{name}.aleo` to the given directory, with the given source as its `src/main.leo`.
+fn write_package(directory: &std::path::Path, name: &str, source: &str) {
+    std::fs::create_dir_all(directory.join("src")).unwrap();
+    let manifest = format!(r#"{{"program": "{name}.aleo", "version": "0.0.0", "license": "MIT"}}"#);
+    std::fs::write(directory.join("program.json"), manifest).unwrap();
+    std::fs::write(directory.join("src/main.leo"), source).unwrap();
+}
+
 /// Path to the only complex Leo program that we have
 /// - relative to source dir - where Cargo.toml is located
 // const PEDERSEN_HASH_PATH: &str = "./examples/pedersen-hash/";
 
 #[test]
 pub fn init_logger() -> Result<()> {
-    crate::cli::helpers::logger::init_logger("test_init_logger", 1)?;
+    crate::cli::helpers::logger::init_logger("test_init_logger", 1, Default::default())?;
     Ok(())
 }
 
 #[test]
 pub fn format_event() -> Result<()> {
-    crate::cli::helpers::logger::init_logger("test_format_event", 1)?;
+    crate::cli::helpers::logger::init_logger("test_format_event", 1, Default::default())?;
     tracing::info!("test");
     Ok(())
 }
 
+#[test]
+pub fn build_package() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+
+    let output = crate::cli::build_package(directory, Default::default())?;
+    assert!(output.persisted);
+    assert!(output.timings.lines > 0);
+    assert!(directory.join("build/main.aleo").exists());
+    Ok(())
+}
+
+#[test]
+pub fn build_bins() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    std::fs::remove_file(directory.join("src/main.leo")).unwrap();
+    std::fs::write(
+        directory.join("program.json"),
+        r#"{"program": "foo.aleo", "version": "0.0.0", "license": "MIT",
+            "bins": [{"name": "a", "path": "src/a.leo"}, {"name": "b", "path": "src/b.leo"}]}"#,
+    )
+    .unwrap();
+    for (name, operator) in [("a", "+"), ("b", "-")] {
+        std::fs::write(
+            directory.join(format!("src/{name}.leo")),
+            format!("program foo.aleo {{\n    transition main(a: u32) -> u32 {{\n        return a {operator} a;\n    }}\n}}\n"),
+        )
+        .unwrap();
+    }
+
+    let output = crate::cli::build_package(directory, Default::default())?;
+    assert_eq!(output.bins.keys().collect::<Vec<_>>(), ["a", "b"]);
+    assert!(output.bins.values().all(|output| output.recompiled));
+    assert!(std::fs::read_to_string(directory.join("build/main.aleo")).unwrap().contains("sub"));
+
+    // `--bin` builds only the selected entry.
+    let options = BuildOptions { bin: Some("a".to_string()), ..Default::default() };
+    let output = crate::cli::build_package(directory, options)?;
+    assert_eq!(output.bins.keys().collect::<Vec<_>>(), ["a"]);
+    assert!(std::fs::read_to_string(directory.join("build/main.aleo")).unwrap().contains("add"));
+    Ok(())
+}
+
+#[test]
+pub fn build_custom_source_dir() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    std::fs::write(
+        directory.join("program.json"),
+        r#"{"program": "foo.aleo", "version": "0.0.0", "license": "MIT", "build": {"source_dir": "sources"}}"#,
+    )
+    .unwrap();
+    std::fs::rename(directory.join("src"), directory.join("sources")).unwrap();
+    assert!(crate::cli::build_package(directory, Default::default())?.recompiled);
+
+    // A missing source directory is an error, rather than a build of `src/`.
+    std::fs::rename(directory.join("sources"), directory.join("src")).unwrap();
+    assert!(crate::cli::build_package(directory, Default::default()).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn build_lib_only() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    std::fs::create_dir_all(directory.join("inputs")).unwrap();
+    let options = || BuildOptions { lib_only: true, ..Default::default() };
+    assert!(crate::cli::build_package(directory, options()).is_err());
+
+    // The inputs are not read, so that a library builds whether or not they match it.
+    std::fs::rename(directory.join("src/main.leo"), directory.join("src/lib.leo")).unwrap();
+    std::fs::write(directory.join("inputs/foo.in"), "[main]\nb: bool = true;\n").unwrap();
+    let output = crate::cli::build_package(directory, options())?;
+    assert!(output.input_ast.is_none());
+    Ok(())
+}
+
+#[test]
+pub fn build_lib_without_main() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    std::fs::rename(directory.join("src/main.leo"), directory.join("src/lib.leo")).unwrap();
+
+    // A library without a program is built, as it is published.
+    assert!(crate::cli::build_package(directory, Default::default())?.recompiled);
+    Ok(())
+}
+
+#[test]
+pub fn build_no_source_files() {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    std::fs::remove_file(directory.join("src/main.leo")).unwrap();
+
+    // Neither an empty source directory nor one holding only other Leo files has anything to build.
+    let error = crate::cli::build_package(directory, Default::default()).unwrap_err().to_string();
+    assert!(error.contains("neither a `main.leo` nor a `lib.leo`"), "{error}");
+    std::fs::write(directory.join("src/helpers.leo"), "program foo.aleo {}\n").unwrap();
+    let error = crate::cli::build_package(directory, Default::default()).unwrap_err().to_string();
+    assert!(error.contains("neither a `main.leo` nor a `lib.leo`"), "{error}");
+}
+
+#[test]
+pub fn build_recovers_corrupt_cache() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    crate::cli::build_package(directory, Default::default())?;
+
+    // A garbage checksum is removed, and the program is recompiled.
+    let corrupt = || std::fs::write(directory.join("outputs/foo.sum"), b"\xff\xfe").unwrap();
+    corrupt();
+    assert!(crate::cli::build_package(directory, Default::default())?.recompiled);
+    assert!(std::fs::read_to_string(directory.join("outputs/foo.sum")).is_ok());
+
+    // `--no-cache-recovery` fails the build instead.
+    corrupt();
+    let options = BuildOptions { no_cache_recovery: true, ..Default::default() };
+    assert!(crate::cli::build_package(directory, options).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn build_frozen() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    let main = directory.join("src/main.leo");
+
+    // A program without a previous build fails `--frozen` with its own error.
+    let frozen = || BuildOptions { frozen: true, ..Default::default() };
+    let error = crate::cli::build_package(directory, frozen()).unwrap_err();
+    assert!(error.to_string().contains("no previous build"), "{error}");
+    crate::cli::build_package(directory, Default::default())?;
+
+    // An unchanged program passes `--frozen`, even without a checksum or with an option disabling incremental builds.
+    assert!(!crate::cli::build_package(directory, frozen())?.recompiled);
+    std::fs::remove_file(directory.join("outputs/foo.sum")).unwrap();
+    assert!(!crate::cli::build_package(directory, frozen())?.recompiled);
+    let snapshot = BuildOptions { enable_initial_ast_snapshot: true, ..frozen() };
+    assert!(!crate::cli::build_package(directory, snapshot)?.recompiled);
+
+    // A changed program fails it, leaving the previous build in place.
+    let instructions = std::fs::read_to_string(directory.join("build/main.aleo")).unwrap();
+    std::fs::write(
+        &main,
+        "program foo.aleo {\n    transition main(a: u32) -> u32 {\n        return a + 1u32;\n    }\n}\n",
+    )
+    .unwrap();
+    let error = crate::cli::build_package(directory, frozen()).unwrap_err();
+    assert!(error.to_string().contains("changed since the last build"), "{error}");
+    assert_eq!(std::fs::read_to_string(directory.join("build/main.aleo")).unwrap(), instructions);
+    Ok(())
+}
+
+#[test]
+pub fn build_manifest_defaults() -> Result<()> {
+    use leo_package::root::CompilerConfig;
+
+    // The manifest enables the flags not given, unless they are disabled.
+    let config = CompilerConfig { optimize: true, deny_warnings: true, ..Default::default() };
+    let options = BuildOptions::default().with_manifest_defaults(&config);
+    assert!(options.enable_dce && options.deny_warnings);
+    let options = BuildOptions { disable_dce: true, no_deny_warnings: true, ..Default::default() };
+    let options = options.with_manifest_defaults(&config);
+    assert!(!options.enable_dce && !options.deny_warnings);
+
+    // Builds with dead code elimination are incremental, and toggling it recompiles the program.
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    let dce = || BuildOptions { enable_dce: true, ..Default::default() };
+    assert!(crate::cli::build_package(directory, dce())?.recompiled);
+    assert!(!crate::cli::build_package(directory, dce())?.recompiled);
+    assert!(crate::cli::build_package(directory, Default::default())?.recompiled);
+    Ok(())
+}
+
+#[test]
+pub fn build_timeout() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+
+    // A build running over its time limit returns an error rather than exiting the process.
+    let options = BuildOptions { timeout: Some(0), ..Default::default() };
+    let error = crate::cli::build_package(directory, options).unwrap_err();
+    assert!(error.to_string().contains("timed out"));
+    Ok(())
+}
+
+#[test]
+pub fn timeout_of_running_work() {
+    use crate::cli::helpers::timeout::Timeout;
+    use std::time::{Duration, Instant};
+
+    // Work running over the time limit, such as a synthesis which never returns, fails at the deadline.
+    let timeout = Timeout::start(1);
+    timeout.add_constraints(42);
+    let start = Instant::now();
+    let error = timeout.run(|| std::thread::sleep(Duration::from_secs(60))).unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(30));
+    assert!(error.to_string().contains("with 42 constraints synthesized so far"));
+
+    // Work within the time limit returns its output.
+    assert_eq!(Timeout::start(60).run(|| 42).unwrap(), 42);
+}
+
+#[test]
+pub fn build_constraint_report() -> Result<()> {
+    let package = temp_package(
+        "foo",
+        "program foo.aleo {\n    transition main(a: u32, b: u32) -> u32 {\n        return a * b;\n    }\n}\n",
+    );
+    let directory = package.path();
+
+    let options = BuildOptions { emit_constraint_report: true, ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    let report = std::fs::read_to_string(directory.join("outputs/foo.constraints.txt")).unwrap();
+    assert!(report.contains("function main: "));
+    assert!(report.contains("  body: "));
+    Ok(())
+}
+
+#[test]
+pub fn build_circuit_out() -> Result<()> {
+    use leo_package::outputs::{CircuitFile, SerializedCircuit};
+
+    let package = temp_package(
+        "foo",
+        "program foo.aleo {\n    transition main(a: u32, b: u32) -> u32 {\n        return a * b;\n    }\n}\n",
+    );
+    let directory = package.path();
+
+    let options = BuildOptions { circuit_out: Some(None), ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    let circuit =
+        SerializedCircuit::from_json_string(&std::fs::read_to_string(directory.join("outputs/foo.json")).unwrap())?;
+    assert_eq!(circuit.program, "foo.aleo");
+    assert_eq!(circuit.checksum, circuit.compute_checksum()?);
+    assert!(!circuit.functions["main"].constraints.is_empty());
+
+    // With `--compress`, the circuit is written with gzip, and the written circuit is read back through gzip.
+    let options = BuildOptions { circuit_out: Some(None), compress: true, ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    assert!(std::fs::read(directory.join("outputs/foo.json.gz")).unwrap().starts_with(&[0x1f, 0x8b]));
+    let compressed = CircuitFile::new("foo").with_compression(true).read_from(&directory.join("outputs"))?;
+    assert_eq!(SerializedCircuit::from_json_string(&compressed)?, circuit);
+    Ok(())
+}
+
+#[test]
+pub fn build_emit_witness() -> Result<()> {
+    use leo_package::outputs::SerializedWitness;
+
+    let package = temp_package(
+        "foo",
+        "program foo.aleo {\n    transition main(public a: u32, b: u32) -> u32 {\n        return a * b;\n    }\n}\n",
+    );
+    let directory = package.path();
+    std::fs::create_dir_all(directory.join("inputs")).unwrap();
+    std::fs::write(directory.join("inputs/foo.in"), "[main]\npublic a: u32 = 3u32;\nb: u32 = 5u32;\n").unwrap();
+
+    // The inputs are named by their registers, and the witness holds the values of the variables of the circuit.
+    let options = BuildOptions { emit_witness: true, ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    let witness = std::fs::read_to_string(directory.join("outputs/foo.witness.json")).unwrap();
+    let witness: SerializedWitness = serde_json::from_str(&witness).unwrap();
+    let main = &witness.functions["main"];
+    assert_eq!(main.registers.keys().collect::<Vec<_>>(), ["r0", "r1"]);
+    assert_eq!((main.registers["r0"].name.as_str(), main.registers["r0"].value.as_str()), ("a", "3u32"));
+    assert_eq!(main.input_assignment.first().map(String::as_str), Some("1"));
+    assert!(!main.aux_assignment.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn build_only_function() -> Result<()> {
+    use leo_package::outputs::SerializedCircuit;
+
+    let package = temp_package(
+        "foo",
+        "program foo.aleo {\n    transition main(a: u32, b: u32) -> u32 {\n        return a * b;\n    }\n\n    \
+         transition other(a: u32) -> u32 {\n        return a + 1u32;\n    }\n}\n",
+    );
+    let directory = package.path();
+
+    // Only the given function is synthesized, into a partial circuit beside the circuit of the program.
+    let options = BuildOptions { only_function: Some("other".to_string()), ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    let circuit = std::fs::read_to_string(directory.join("outputs/foo.other.partial.json")).unwrap();
+    let circuit = SerializedCircuit::from_json_string(&circuit)?;
+    assert_eq!(circuit.only_function.as_deref(), Some("other"));
+    assert_eq!(circuit.functions.keys().collect::<Vec<_>>(), ["other"]);
+    assert!(!directory.join("outputs/foo.json").exists());
+
+    // A function which isn't in the program is an error.
+    let options = BuildOptions { only_function: Some("missing".to_string()), ..Default::default() };
+    assert!(crate::cli::build_package(directory, options).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn build_manifest_path() -> Result<()> {
+    use crate::cli::{commands::Command, context::Context, Build};
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+
+    // The package is found through the manifest, whatever the directory of the context.
+    let build = |manifest_path: std::path::PathBuf| Build { manifest_path: Some(manifest_path), ..Default::default() };
+    let context = Context::new(Some(std::env::temp_dir()))?;
+    create_session_if_not_set_then(|_| build(directory.join("program.json")).apply(context, ()))?;
+    assert!(directory.join("build/main.aleo").exists());
+
+    // A file which isn't a manifest fails the build.
+    assert!(build(directory.join("src/main.leo")).apply(Context::new(None)?, ()).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn build_workspace() -> Result<()> {
+    use crate::cli::{commands::Command, context::Context, Build};
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    let workspace = tempfile::tempdir().unwrap();
+    std::fs::write(workspace.path().join("program.json"), r#"{"workspace": {"members": ["foo"]}}"#).unwrap();
+    let member = workspace.path().join("foo");
+    write_package(
+        &member,
+        "foo",
+        "import bar.leo;\nprogram foo.aleo {\n    transition main(a: u32) -> u32 {\n        return bar.leo/id(a);\n    \
+         }\n}\n",
+    );
+    std::fs::create_dir_all(member.join("imports")).unwrap();
+    std::fs::write(member.join("imports/bar.leo"), IDENTITY_PROGRAM.replace("foo", "bar").replace("main", "id"))
+        .unwrap();
+
+    // The imports of a member are read from the member, without changing the cwd.
+    let cwd = std::env::current_dir().unwrap();
+    let build = Build { workspace: true, ..Default::default() };
+    create_session_if_not_set_then(|_| build.apply(Context::new(Some(workspace.path().to_path_buf()))?, ()))?;
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+    assert!(member.join("build/imports/bar.aleo").exists());
+    Ok(())
+}
+
+#[test]
+pub fn build_workspace_content_addressed() -> Result<()> {
+    use crate::cli::{commands::Command, context::Context, Build};
+    use leo_package::outputs::{CircuitFile, SerializedCircuit};
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    // Two members building the same program into the outputs directory of the workspace.
+    let workspace = tempfile::tempdir().unwrap();
+    std::fs::write(workspace.path().join("program.json"), r#"{"workspace": {"members": ["a", "b"]}}"#).unwrap();
+    write_package(&workspace.path().join("a"), "foo", IDENTITY_PROGRAM);
+    write_package(&workspace.path().join("b"), "foo", IDENTITY_PROGRAM);
+
+    let outputs = workspace.path().join("outputs");
+    let options = BuildOptions {
+        circuit_out: Some(None),
+        content_addressed: true,
+        output_dir: Some(outputs.clone()),
+        ..Default::default()
+    };
+    let build = Build { options, workspace: true, ..Default::default() };
+    create_session_if_not_set_then(|_| build.apply(Context::new(Some(workspace.path().to_path_buf()))?, ()))?;
+
+    // The identical circuits of the members are stored once, and the pointer to them reads back as the circuit.
+    let blobs = std::fs::read_dir(outputs.join(".circuits")).unwrap().collect::<Vec<_>>();
+    assert_eq!(blobs.len(), 1);
+    let circuit = CircuitFile::new("foo").with_content_addressing(true).read_from(&outputs)?;
+    assert_eq!(SerializedCircuit::from_json_string(&circuit)?.program, "foo.aleo");
+    Ok(())
+}
+
+#[test]
+pub fn build_git_ref() -> Result<()> {
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    let git = |args: &[&str]| {
+        let mut command = std::process::Command::new("git");
+        command.arg("-C").arg(directory).args(["-c", "user.name=leo", "-c", "user.email=leo@aleo.org"]);
+        assert!(command.args(args).output().unwrap().status.success());
+    };
+    git(&["init", "--quiet"]);
+    git(&["add", "."]);
+    git(&["commit", "--quiet", "--message", "foo"]);
+
+    // The artifacts of the revision are written to the output directory, and never to the working tree.
+    let output_dir = tempfile::tempdir().unwrap();
+    let output_dir = output_dir.path().join("out");
+    let options =
+        BuildOptions { git_ref: Some("HEAD".to_string()), output_dir: Some(output_dir.clone()), ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    assert!(output_dir.join("build/main.aleo").exists());
+    assert!(output_dir.join("foo.sum").exists());
+    assert!(!directory.join("build").exists() && !directory.join("outputs").exists());
+
+    // Otherwise, they are written to the outputs directory of the package, and the copy of the revision is removed.
+    let options = BuildOptions { git_ref: Some("HEAD".to_string()), ..Default::default() };
+    crate::cli::build_package(directory, options)?;
+    assert!(directory.join("outputs/build/main.aleo").exists());
+    assert!(directory.join("outputs/foo.sum").exists());
+    assert!(!directory.join("build").exists());
+    let suffix = format!("-{}", std::process::id());
+    assert!(!std::fs::read_dir(std::env::temp_dir()).unwrap().filter_map(|entry| entry.ok()).any(|entry| {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        name.starts_with("leo-git-ref-") && name.ends_with(&suffix)
+    }));
+    Ok(())
+}
+
+#[test]
+pub fn bundle_round_trip() -> Result<()> {
+    use crate::cli::{commands::Command, context::Context, Bundle, Unbundle};
+
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let directory = package.path();
+    crate::cli::build_package(directory, Default::default())?;
+    let context = || Context::new(Some(directory.to_path_buf()));
+    let bundle = Bundle { output: None }.apply(context()?, ())?;
+
+    // The extracted files are those of the build.
+    let extracted = directory.join("extracted");
+    let unbundle = |bundle: &std::path::Path| {
+        Unbundle { bundle: bundle.to_path_buf(), output_dir: Some(extracted.clone()) }.apply(context()?, ())
+    };
+    let metadata = unbundle(&bundle)?;
+    assert_eq!(metadata.package_name, "foo");
+    assert_eq!(metadata.files.len(), 2);
+    for file in ["build/main.aleo", "outputs/foo.sum"] {
+        assert_eq!(std::fs::read(directory.join(file)).unwrap(), std::fs::read(extracted.join(file)).unwrap());
+    }
+
+    // A file not matching its checksum fails the extraction.
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&bundle).unwrap()).unwrap();
+    let tampered_path = directory.join("tampered.leobundle");
+    let mut tampered = zip::ZipWriter::new(std::fs::File::create(&tampered_path).unwrap());
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).unwrap();
+        let mut contents = Vec::new();
+        std::io::Read::read_to_end(&mut file, &mut contents).unwrap();
+        if file.name() == "build/main.aleo" {
+            contents.extend(b"\n");
+        }
+        tampered.start_file(file.name(), Default::default()).unwrap();
+        std::io::Write::write_all(&mut tampered, &contents).unwrap();
+    }
+    tampered.finish().unwrap();
+    assert!(unbundle(&tampered_path).is_err());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+pub fn build_read_only_package() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let package = temp_package("foo", IDENTITY_PROGRAM);
+    let package = package.path();
+    std::fs::set_permissions(package, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+    let directory = tempfile::tempdir().unwrap();
+    let output_dir = directory.path().join("out");
+    let options = BuildOptions { output_dir: Some(output_dir.clone()), ..Default::default() };
+    let output = crate::cli::build_package(package, options);
+    std::fs::set_permissions(package, std::fs::Permissions::from_mode(0o755)).unwrap();
+    assert!(output?.persisted);
+
+    // Only the external output directory is written to, including the checksum and the compiled program.
+    let mut entries = std::fs::read_dir(package).unwrap().map(|entry| entry.unwrap().file_name()).collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, ["program.json", "src"]);
+    assert!(output_dir.join("foo.sum").exists());
+    assert!(output_dir.join("build/main.aleo").exists());
+    Ok(())
+}
+
+#[test]
+pub fn build_is_reproducible() -> Result<()> {
+    // The same program is built in two packages, whose JSON artifacts must be byte-identical.
+    let build = || -> Result<Vec<Vec<u8>>> {
+        let package = temp_package(
+            "foo",
+            "program foo.aleo {\n    struct Point {\n        x: u32,\n        y: u32,\n    }\n\n    transition main(a: u32, b: u32) -> Point {\n        return Point { x: a, y: b };\n    }\n}\n",
+        );
+        let directory = package.path();
+
+        let options = BuildOptions {
+            emit_stats: true,
+            print_ast: Some(None),
+            enable_initial_ast_snapshot: true,
+            ..Default::default()
+        };
+        crate::cli::build_package(directory, options)?;
+        let artifacts =
+            ["build/main.aleo", "outputs/foo.stats.json", "outputs/foo.ast.json", "outputs/foo.initial_ast.json"]
+                .iter()
+                .map(|path| std::fs::read(directory.join(path)).unwrap())
+                .collect();
+
+        Ok(artifacts)
+    };
+    assert_eq!(build()?, build()?);
+    Ok(())
+}
+
 // todo (collin): uncomment after refactor
 // #[test]
 // pub fn build_pedersen_hash() -> Result<()> {
@@ -225,3 +754,212 @@ pub fn format_event() -> Result<()> {
 
     Ok(context)
 } */
+
+#[test]
+pub fn build_options_from_env_vars() {
+    use clap::{Arg, ArgAction, Args, Command};
+
+    let build = Command::new("build").arg(Arg::new("watch").long("watch").action(ArgAction::SetTrue));
+    let command = Command::new("leo").subcommand(BuildOptions::augment_args(build));
+    let env = |command: &Command, id: &str| {
+        let arg = command.find_subcommand("build").unwrap().get_arguments().find(|arg| arg.get_id() == id).unwrap();
+        arg.get_env().map(|env| env.to_string_lossy().into_owned())
+    };
+
+    // The build options are read from variables named after their flags, in the subcommands taking them.
+    let with_env_vars = BuildOptions::with_env_vars(command.clone());
+    assert_eq!(env(&with_env_vars, "output_dir").as_deref(), Some("LEO_OUTPUT_DIR"));
+    assert_eq!(env(&with_env_vars, "input_name").as_deref(), Some("LEO_INPUT"));
+    assert_eq!(env(&with_env_vars, "max_constraints").as_deref(), Some("LEO_MAX_CONSTRAINTS"));
+    // The other options of a command aren't.
+    assert_eq!(env(&with_env_vars, "watch"), None);
+
+    // A flag takes precedence over its variable. The variables, which are read as the command is built, have a
+    // prefix of their own, so that no other test reads them.
+    std::env::set_var("LEO_TEST_ENV_VARS_WARN_PROGRAM_SIZE", "100");
+    std::env::set_var("LEO_TEST_ENV_VARS_DENY_WARNINGS", "1");
+    let command = BuildOptions::with_env_var_prefix(command, "LEO_TEST_ENV_VARS_");
+    std::env::remove_var("LEO_TEST_ENV_VARS_WARN_PROGRAM_SIZE");
+    std::env::remove_var("LEO_TEST_ENV_VARS_DENY_WARNINGS");
+    let warn_program_size = |args: &[&str]| {
+        let matches = command.clone().try_get_matches_from(args).unwrap();
+        let build = matches.subcommand_matches("build").unwrap();
+        (build.get_one::<u64>("warn_program_size").copied(), build.get_flag("deny_warnings"))
+    };
+    assert_eq!(warn_program_size(&["leo", "build"]), (Some(100), true));
+    assert_eq!(warn_program_size(&["leo", "build", "--warn-program-size", "200"]), (Some(200), true));
+}
+
+#[test]
+pub fn env_vars_only_for_build_options() {
+    use crate::cli::CLI;
+    use clap::CommandFactory;
+
+    let command = BuildOptions::with_env_vars(CLI::command());
+    let env = |path: &[&str], id: &str| {
+        let command = path.iter().fold(&command, |command, name| command.find_subcommand(name).unwrap());
+        let arg = command.get_arguments().find(|arg| arg.get_id() == id).unwrap();
+        arg.get_env().map(|env| env.to_string_lossy().into_owned())
+    };
+
+    // `--seed` of the build is read from `LEO_SEED`, but the seed of a new account never is.
+    assert_eq!(env(&["build"], "seed").as_deref(), Some("LEO_SEED"));
+    assert_eq!(env(&["account", "new"], "seed"), None);
+}
+
+#[test]
+pub fn workspace_build_order() -> Result<()> {
+    use crate::cli::commands::build::workspace::workspace_build_order;
+
+    let members = ["game", "board", "lib"].map(String::from);
+
+    // The game imports the board and the library, and the board imports the library.
+    let order = workspace_build_order(&members, &[vec![1, 2], vec![2], vec![]])?;
+    assert_eq!(order, vec![2, 1, 0]);
+
+    // Members importing each other can't be built.
+    assert!(workspace_build_order(&members, &[vec![1], vec![0], vec![]]).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn workspace_members_to_build() {
+    use crate::cli::commands::build::workspace::members_to_build;
+
+    // The game imports the board and the library, and the board imports the library.
+    let (order, dependencies) = ([2, 1, 0], [vec![1, 2], vec![2], vec![]]);
+
+    // A changed member is built along with the members importing it.
+    assert_eq!(members_to_build(&order, &dependencies, &[false, false, true]), vec![true, true, true]);
+    assert_eq!(members_to_build(&order, &dependencies, &[false, true, false]), vec![true, true, false]);
+    assert_eq!(members_to_build(&order, &dependencies, &[true, false, false]), vec![true, false, false]);
+    assert_eq!(members_to_build(&order, &dependencies, &[false, false, false]), vec![false, false, false]);
+}
+
+#[test]
+pub fn imported_programs() {
+    use crate::cli::commands::build::imported_programs;
+
+    let source = "import board.leo;\n  import lib.leo;\n// The game.\nprogram game.aleo {\n}\n";
+    assert_eq!(imported_programs(source), ["board", "lib"]);
+    assert!(imported_programs("program game.aleo {\n}\n").is_empty());
+}
+
+#[test]
+pub fn diff_format_change() {
+    use crate::cli::commands::diff::format_change;
+
+    assert_eq!(format_change(10, 12), "+2, +20.00%");
+    assert_eq!(format_change(8, 6), "-2, -25.00%");
+    // A statistic that was zero has no percentage change.
+    assert_eq!(format_change(0, 3), "+3");
+}
+
+#[test]
+pub fn resolve_input_includes() -> Result<()> {
+    use crate::cli::commands::build::input::resolve_input_includes;
+
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    std::fs::create_dir_all(directory.join("common")).unwrap();
+    std::fs::write(directory.join("common/preamble.in"), "[main]\na: u32 = 1u32;").unwrap();
+    let input = "include \"common/preamble.in\";\nb: u32 = 2u32;\n";
+    let resolved = resolve_input_includes(input, &directory.join("main.in"), &mut Vec::new())?;
+    assert_eq!(resolved, "[main]\na: u32 = 1u32;\nb: u32 = 2u32;\n");
+
+    // Files including each other are an error, as are missing files.
+    std::fs::write(directory.join("a.in"), "include \"b.in\";\n").unwrap();
+    std::fs::write(directory.join("b.in"), "include \"a.in\";\n").unwrap();
+    assert!(resolve_input_includes("include \"b.in\";\n", &directory.join("a.in"), &mut Vec::new()).is_err());
+    assert!(resolve_input_includes("include \"missing.in\";\n", &directory.join("main.in"), &mut Vec::new()).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn substitute_env_vars() -> Result<()> {
+    use crate::cli::commands::build::input::substitute_env_vars;
+    use std::path::Path;
+
+    // The variables are looked up in a map rather than the environment, which other tests may read concurrently.
+    let var = |name: &str| (name == "LEO_TEST_INPUT_A").then(|| "1u32".to_string());
+    let input = "[main]\npublic a: u32 = ${LEO_TEST_INPUT_A};\nb: u32 = 2u32;\n";
+    let (substituted, env_inputs) = substitute_env_vars(input, Path::new("main.in"), var)?;
+    assert_eq!(substituted, "[main]\npublic a: u32 = 1u32;\nb: u32 = 2u32;\n");
+    assert_eq!(env_inputs, vec![("[main] a".to_string(), "LEO_TEST_INPUT_A".to_string())]);
+
+    // Unset variables are an error.
+    assert!(substitute_env_vars("[main]\na: u32 = ${LEO_TEST_INPUT_UNSET};\n", Path::new("main.in"), var).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn translate_structured_input() -> Result<()> {
+    use crate::cli::commands::{build::input::translate_structured_input, InputFormat};
+    use leo_ast::{NodeBuilder, Variant};
+    use leo_errors::emitter::Handler;
+    use leo_span::symbol::create_session_if_not_set_then;
+    use std::path::Path;
+
+    create_session_if_not_set_then(|_| {
+        let source =
+            "program foo.aleo {\n    transition main(public a: u32, b: bool) -> u32 {\n        return a;\n    }\n}\n";
+        let ast = leo_parser::parse_ast(&Handler::default(), &NodeBuilder::default(), source, Default::default())?;
+        let transitions = ast
+            .into_repr()
+            .program_scopes
+            .into_values()
+            .flat_map(|scope| scope.functions)
+            .filter(|(_, function)| matches!(function.variant, Variant::Transition))
+            .map(|(name, function)| (name.to_string(), function))
+            .collect();
+
+        // The inputs are written in the order of the parameters, with the suffixes of their types.
+        let expected = "[main]\npublic a: u32 = 1u32;\nb: bool = true;\n";
+        let json = r#"{"main": {"b": true, "a": 1}}"#;
+        assert_eq!(translate_structured_input(json, InputFormat::Json, Path::new("foo.json"), &transitions)?, expected);
+        let toml = "[main]\na = \"1u32\"\nb = true\n";
+        assert_eq!(translate_structured_input(toml, InputFormat::Toml, Path::new("foo.toml"), &transitions)?, expected);
+
+        // Unknown transitions and inputs, missing inputs, and values of the wrong type are errors.
+        for json in [
+            r#"{"other": {}}"#,
+            r#"{"main": {"a": 1}}"#,
+            r#"{"main": {"a": 1, "b": true, "c": 2}}"#,
+            r#"{"main": {"a": "1u8", "b": true}}"#,
+            r#"{"main": {"a": 1, "b": 0}}"#,
+        ] {
+            assert!(translate_structured_input(json, InputFormat::Json, Path::new("foo.json"), &transitions).is_err());
+        }
+        Ok(())
+    })
+}
+
+#[test]
+pub fn resolve_import_paths() -> Result<()> {
+    use crate::cli::commands::build::resolve_import_paths;
+    use leo_package::root::ImportsConfig;
+
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    std::fs::create_dir_all(directory.join("app")).unwrap();
+    std::fs::create_dir_all(directory.join("shared")).unwrap();
+    std::fs::write(directory.join("shared/util.leo"), "").unwrap();
+    let package_path = directory.join("app");
+
+    let mut imports = ImportsConfig::default();
+    imports.paths.insert("util".to_string(), "../shared/util.leo".into());
+
+    // A file outside of the package can only be imported if its directory is declared as a root.
+    assert!(resolve_import_paths(&package_path, &imports).is_err());
+    imports.roots.push("../shared".into());
+    let import_paths = resolve_import_paths(&package_path, &imports)?;
+    assert_eq!(import_paths["util"], directory.join("shared/util.leo").canonicalize().unwrap());
+
+    // Missing files and files not named after their import are rejected.
+    imports.paths.insert("util".to_string(), "../shared/missing.leo".into());
+    assert!(resolve_import_paths(&package_path, &imports).is_err());
+    std::fs::write(directory.join("shared/other.leo"), "").unwrap();
+    imports.paths.insert("util".to_string(), "../shared/other.leo".into());
+    assert!(resolve_import_paths(&package_path, &imports).is_err());
+    Ok(())
+}
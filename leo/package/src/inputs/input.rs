@@ -16,7 +16,7 @@
 
 //! The `program.in` file.
 
-use crate::inputs::INPUTS_DIRECTORY_NAME;
+use crate::{inputs::INPUTS_DIRECTORY_NAME, package::PackageTemplate};
 
 use leo_errors::{PackageError, Result};
 
@@ -32,15 +32,29 @@ use std::{
 };
 
 pub static INPUT_FILE_EXTENSION: &str = ".in";
+pub static STATE_FILE_EXTENSION: &str = ".state";
 
 #[derive(Deserialize)]
 pub struct InputFile {
     pub package_name: String,
+    #[serde(default)]
+    pub template: PackageTemplate,
 }
 
 impl InputFile {
     pub fn new(package_name: &str) -> Self {
-        Self { package_name: package_name.to_string() }
+        Self { package_name: package_name.to_string(), template: PackageTemplate::default() }
+    }
+
+    /// Returns the input file written from the given starter template.
+    pub fn with_template(mut self, template: PackageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Returns the input file `{package_name}.{input_name}.in` of a named input scenario.
+    pub fn named(package_name: &str, input_name: &str) -> Self {
+        Self::new(&format!("{package_name}.{input_name}"))
     }
 
     pub fn filename(&self) -> String {
@@ -71,14 +85,35 @@ impl InputFile {
     }
 
     fn template(&self) -> String {
-        format!(
-            r#"// The program input for {}/src/main.leo
+        let name = &self.package_name;
+        match self.template {
+            PackageTemplate::Token => format!(
+                r#"// The program input for {name}/src/main.leo
+[mint]
+receiver: address = aleo1ptqvxu4gjfge8tuhgq2pqap0u5pms4p97gwhu7dwngxshpfzcszsswzpzd;
+amount: u64 = 100u64;
+"#
+            ),
+            PackageTemplate::Test => format!(
+                r#"// The program input for {name}/src/main.leo
+[main]
+public a: u32 = 1u32;
+b: u32 = 2u32;
+
+[test_add]
+a: u32 = 1u32;
+b: u32 = 2u32;
+expected: u32 = 3u32;
+"#
+            ),
+            PackageTemplate::Hello | PackageTemplate::Lib => format!(
+                r#"// The program input for {name}/src/main.leo
 [main]
 public a: u32 = 1u32;
 b: u32 = 2u32;
-"#,
-            self.package_name
-        )
+"#
+            ),
+        }
     }
 
     pub fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
@@ -27,10 +27,73 @@ pub mod source;
 
 use leo_errors::{PackageError, Result};
 
-use std::{fs, fs::ReadDir, path::PathBuf};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::{
+    ffi::OsString,
+    fs,
+    fs::{File, ReadDir},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
 
 pub static LEO_FILE_EXTENSION: &str = ".leo";
 
+/// The extension of the temporary files written before being renamed into place.
+pub static TEMPORARY_FILE_EXTENSION: &str = ".tmp";
+
+/// The extension of gzip-compressed files.
+pub static GZIP_FILE_EXTENSION: &str = ".gz";
+
+/// The bytes every gzip-compressed file starts with.
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+/// Compresses the given bytes with gzip.
+pub(crate) fn gzip(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+/// Reads the file at `path` to a string, decompressing it first if it is gzip-compressed.
+pub(crate) fn read_to_string_decompressed(path: &Path) -> io::Result<String> {
+    let bytes = fs::read(path)?;
+    if !bytes.starts_with(&GZIP_MAGIC_BYTES) {
+        return String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    let mut string = String::new();
+    GzDecoder::new(bytes.as_slice()).read_to_string(&mut string)?;
+    Ok(string)
+}
+
+/// Writes `contents` to `path` by writing a temporary file in the same directory and renaming it into place.
+/// The rename is atomic, so readers never observe a partially written file,
+/// and a write that is interrupted leaves the previous file intact.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temporary_path = write_temporary_file(path, contents)?;
+    fs::rename(&temporary_path, path).map_err(|err| {
+        // The temporary file is useless if it cannot be renamed.
+        let _ = fs::remove_file(&temporary_path);
+        err
+    })
+}
+
+/// Writes `contents` to a temporary file next to `path`, e.g. `.foo.sum.tmp`, and returns its path.
+fn write_temporary_file(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let file_name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing file name"))?;
+    let mut temporary_file_name = OsString::from(".");
+    temporary_file_name.push(file_name);
+    temporary_file_name.push(TEMPORARY_FILE_EXTENSION);
+    let temporary_path = path.with_file_name(temporary_file_name);
+
+    let mut file = File::create(&temporary_path)?;
+    file.write_all(contents)?;
+    // The contents must be on disk before the rename, or a crash could leave an empty file in place.
+    file.sync_all()?;
+
+    Ok(temporary_path)
+}
+
 pub(crate) fn parse_file_paths(directory: ReadDir, file_paths: &mut Vec<PathBuf>) -> Result<()> {
     for file_entry in directory {
         let file_entry = file_entry.map_err(PackageError::failed_to_get_leo_file_entry)?;
@@ -62,3 +125,42 @@ pub(crate) fn parse_file_paths(directory: ReadDir, file_paths: &mut Vec<PathBuf>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::ChecksumFile;
+
+    #[test]
+    fn test_interrupted_write_preserves_previous_file() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let checksum_file = ChecksumFile::new("foo");
+        let path = directory.path().join(checksum_file.file_name());
+        checksum_file.write_to(&path, "sha256:old".to_string()).unwrap();
+
+        // Simulate a crash between writing the temporary file and renaming it into place.
+        let temporary_path = write_temporary_file(&path, b"sha256:new").unwrap();
+        assert_eq!(checksum_file.read_from(&path).unwrap(), "sha256:old");
+
+        // The next write replaces both the previous file and the leftover temporary file.
+        checksum_file.write_to(&path, "sha256:new".to_string()).unwrap();
+        assert_eq!(checksum_file.read_from(&path).unwrap(), "sha256:new");
+        assert!(!temporary_path.exists());
+    }
+
+    #[test]
+    fn test_read_decompresses_gzip() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let path = directory.path().join("foo.json.gz");
+        write_atomically(&path, &gzip(b"{}").unwrap()).unwrap();
+        assert!(fs::read(&path).unwrap().starts_with(&GZIP_MAGIC_BYTES));
+        assert_eq!(read_to_string_decompressed(&path).unwrap(), "{}");
+
+        // Uncompressed files are read as they are.
+        let path = directory.path().join("foo.json");
+        write_atomically(&path, b"{}").unwrap();
+        assert_eq!(read_to_string_decompressed(&path).unwrap(), "{}");
+    }
+}
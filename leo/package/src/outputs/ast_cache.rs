@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The AST cache file.
+
+use crate::{
+    gzip,
+    outputs::OUTPUTS_DIRECTORY_NAME,
+    read_to_string_decompressed,
+    write_atomically,
+    GZIP_FILE_EXTENSION,
+};
+use leo_errors::{PackageError, Result};
+
+use serde::Deserialize;
+use std::{
+    borrow::Cow,
+    fs::{self},
+    path::Path,
+};
+
+pub static AST_CACHE_FILE_EXTENSION: &str = ".ast_cache.json";
+
+#[derive(Deserialize)]
+pub struct AstCacheFile {
+    pub package_name: String,
+    /// Whether the file is written compressed with gzip.
+    #[serde(default)]
+    pub compressed: bool,
+}
+
+impl AstCacheFile {
+    pub fn new(package_name: &str) -> Self {
+        Self { package_name: package_name.to_string(), compressed: false }
+    }
+
+    /// Returns the file with the given compression, which adds the `.gz` extension to its file name.
+    pub fn with_compression(self, compressed: bool) -> Self {
+        Self { compressed, ..self }
+    }
+
+    /// Returns the file name of the AST cache file, e.g. `foo.ast_cache.json`, or `foo.ast_cache.json.gz` if compressed.
+    pub fn file_name(&self) -> String {
+        match self.compressed {
+            true => format!("{}{AST_CACHE_FILE_EXTENSION}{GZIP_FILE_EXTENSION}", self.package_name),
+            false => format!("{}{AST_CACHE_FILE_EXTENSION}", self.package_name),
+        }
+    }
+
+    pub fn exists_at(&self, path: &Path) -> bool {
+        let path = self.setup_file_path(path);
+        path.exists()
+    }
+
+    /// Reads the cached AST from the given file path if it exists.
+    pub fn read_from(&self, path: &Path) -> Result<String> {
+        let path = self.setup_file_path(path);
+
+        // Compressed files are detected by their contents, so they are read regardless of the extension.
+        let string = read_to_string_decompressed(&path)
+            .map_err(|_| PackageError::failed_to_read_ast_cache_file(path.into_owned()))?;
+        Ok(string)
+    }
+
+    /// Writes the given cached AST to a file.
+    pub fn write_to(&self, path: &Path, ast: String) -> Result<()> {
+        let path = self.setup_file_path(path);
+        let contents = match self.compressed {
+            true => gzip(ast.as_bytes()).map_err(PackageError::io_error_ast_cache_file)?,
+            false => ast.into_bytes(),
+        };
+        write_atomically(&path, &contents).map_err(PackageError::io_error_ast_cache_file)?;
+        Ok(())
+    }
+
+    /// Removes the cached AST at the given path if it exists. Returns `true` on success,
+    /// `false` if the file doesn't exist, and `Error` if the file system fails during operation.
+    pub fn remove(&self, path: &Path) -> Result<bool> {
+        let path = self.setup_file_path(path);
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        fs::remove_file(&path).map_err(|_| PackageError::failed_to_remove_ast_cache_file(path.into_owned()))?;
+        Ok(true)
+    }
+
+    fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut().push(self.file_name());
+        }
+        path
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The build manifest file, listing the artifacts on disk after a build.
+
+use crate::{outputs::OUTPUTS_DIRECTORY_NAME, write_atomically};
+use leo_errors::{PackageError, Result};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    fs::{self},
+    path::{Path, PathBuf},
+};
+
+pub static BUILD_MANIFEST_FILE_EXTENSION: &str = ".build.json";
+
+/// A file produced by a build, as it is on disk.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildArtifact {
+    /// The kind of the artifact, e.g. `program` or `checksum`.
+    pub kind: String,
+    /// The absolute path of the artifact.
+    pub path: PathBuf,
+    /// The size of the artifact in bytes.
+    pub size: u64,
+    /// The SHA-256 hash of the artifact, in hex.
+    pub sha256: String,
+}
+
+impl BuildArtifact {
+    /// Describes the file at the given path as it is on disk.
+    pub fn from_file(kind: &str, path: &Path) -> Result<Self> {
+        let error = |e| PackageError::failed_to_read_build_artifact(path.display(), e);
+        let contents = fs::read(path).map_err(error)?;
+        Ok(Self {
+            kind: kind.to_string(),
+            path: path.canonicalize().map_err(error)?,
+            size: contents.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(&contents)),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BuildManifestFile {
+    pub package_name: String,
+}
+
+impl BuildManifestFile {
+    pub fn new(package_name: &str) -> Self {
+        Self { package_name: package_name.to_string() }
+    }
+
+    /// Returns the file name of the build manifest, e.g. `foo.build.json`.
+    pub fn file_name(&self) -> String {
+        format!("{}{BUILD_MANIFEST_FILE_EXTENSION}", self.package_name)
+    }
+
+    pub fn exists_at(&self, path: &Path) -> bool {
+        let path = self.setup_file_path(path);
+        path.exists()
+    }
+
+    /// Reads the artifacts listed in the build manifest at the given file path.
+    pub fn read_from(&self, path: &Path) -> Result<Vec<BuildArtifact>> {
+        let path = self.setup_file_path(path);
+
+        let string = fs::read_to_string(&path)
+            .map_err(|_| PackageError::failed_to_read_build_manifest_file(path.clone().into_owned()))?;
+        let manifest: BuildManifest = serde_json::from_str(&string)
+            .map_err(|_| PackageError::failed_to_read_build_manifest_file(path.into_owned()))?;
+        Ok(manifest.artifacts)
+    }
+
+    /// Writes the given artifacts to a file.
+    pub fn write_to(&self, path: &Path, artifacts: Vec<BuildArtifact>) -> Result<()> {
+        let path = self.setup_file_path(path);
+        let manifest = serde_json::to_string_pretty(&BuildManifest { artifacts })
+            .map_err(PackageError::io_error_build_manifest_file)?;
+        write_atomically(&path, manifest.as_bytes()).map_err(PackageError::io_error_build_manifest_file)?;
+        Ok(())
+    }
+
+    /// Removes the build manifest at the given path if it exists. Returns `true` on success,
+    /// `false` if the file doesn't exist, and `Error` if the file system fails during operation.
+    pub fn remove(&self, path: &Path) -> Result<bool> {
+        let path = self.setup_file_path(path);
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        fs::remove_file(&path).map_err(|_| PackageError::failed_to_remove_build_manifest_file(path.into_owned()))?;
+        Ok(true)
+    }
+
+    fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut().push(self.file_name());
+        }
+        path
+    }
+}
+
+/// The contents of a build manifest file.
+#[derive(Serialize, Deserialize)]
+struct BuildManifest {
+    artifacts: Vec<BuildArtifact>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_manifest_lists_artifacts() {
+        let directory = tempfile::tempdir().unwrap();
+        let aleo_file_path = directory.path().join("main.aleo");
+        fs::write(&aleo_file_path, "abc").unwrap();
+
+        let artifact = BuildArtifact::from_file("program", &aleo_file_path).unwrap();
+        assert_eq!(artifact.path, aleo_file_path.canonicalize().unwrap());
+        assert_eq!(artifact.size, 3);
+        assert_eq!(artifact.sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        let build_manifest_file = BuildManifestFile::new("foo");
+        let path = directory.path().join(build_manifest_file.file_name());
+        build_manifest_file.write_to(&path, vec![artifact.clone()]).unwrap();
+        assert_eq!(build_manifest_file.read_from(&path).unwrap(), vec![artifact]);
+    }
+}
@@ -16,30 +16,94 @@
 
 //! The build checksum file.
 
-use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use crate::{outputs::OUTPUTS_DIRECTORY_NAME, write_atomically};
 use leo_errors::{PackageError, Result};
 
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::{
     borrow::Cow,
-    fs::{
-        File,
-        {self},
-    },
-    io::Write,
+    fmt,
+    fs::{self},
     path::Path,
 };
 
 pub static CHECKSUM_FILE_EXTENSION: &str = ".sum";
 
+/// The extension of the checksum file recording the compiled programs the proving and verifying keys were
+/// synthesized for.
+pub static KEYS_CHECKSUM_FILE_EXTENSION: &str = ".keys.sum";
+
+/// The prefix of the line of a checksum file recording the version of the compiler that built the program.
+pub static COMPILER_VERSION_PREFIX: &str = "leo ";
+
+/// The hash algorithm used to compute checksums.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        })
+    }
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the checksum of the given bytes, prefixed with the name of the algorithm, e.g. `sha256:...`.
+    /// The prefix ensures that checksums computed with different algorithms never compare equal.
+    pub fn checksum(&self, bytes: &[u8]) -> String {
+        let hash = match self {
+            Self::Sha256 => format!("{:x}", Sha256::digest(bytes)),
+            Self::Blake3 => blake3::hash(bytes).to_hex().to_string(),
+        };
+        format!("{self}:{hash}")
+    }
+
+    /// Returns the checksum of a program checksum combined with the given files, as pairs of names and contents.
+    pub fn combined_checksum(&self, program_checksum: &str, files: &[(String, Vec<u8>)]) -> String {
+        let mut bytes = program_checksum.as_bytes().to_vec();
+        for (name, contents) in files {
+            // The lengths separate the names and contents, so that moving bytes between them changes the checksum.
+            bytes.extend(name.len().to_le_bytes());
+            bytes.extend(name.as_bytes());
+            bytes.extend(contents.len().to_le_bytes());
+            bytes.extend(contents);
+        }
+        self.checksum(&bytes)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct ChecksumFile {
     pub package_name: String,
+    /// The file name replacing the one derived from the package name, if one is given.
+    #[serde(default)]
+    pub custom_file_name: Option<String>,
 }
 
 impl ChecksumFile {
     pub fn new(package_name: &str) -> Self {
-        Self { package_name: package_name.to_string() }
+        Self { package_name: package_name.to_string(), custom_file_name: None }
+    }
+
+    /// Returns the file with the given file name, used in place of the one derived from the package name.
+    pub fn with_file_name(self, file_name: &str) -> Self {
+        Self { custom_file_name: Some(file_name.to_string()), ..self }
+    }
+
+    /// Returns the file name of the checksum file, e.g. `foo.sum`, unless a custom file name is given.
+    pub fn file_name(&self) -> String {
+        match &self.custom_file_name {
+            Some(file_name) => file_name.clone(),
+            None => format!("{}{CHECKSUM_FILE_EXTENSION}", self.package_name),
+        }
     }
 
     pub fn exists_at(&self, path: &Path) -> bool {
@@ -47,21 +111,46 @@ impl ChecksumFile {
         path.exists()
     }
 
-    /// Reads the checksum from the given file path if it exists.
+    /// Reads the checksum from the given file path if it exists, in its canonical form.
     pub fn read_from(&self, path: &Path) -> Result<String> {
         let path = self.setup_file_path(path);
 
         let string =
             fs::read_to_string(&path).map_err(|_| PackageError::failed_to_read_checksum_file(path.into_owned()))?;
-        Ok(string)
+        Ok(Self::normalize(&string))
     }
 
-    /// Writes the given checksum to a file.
+    /// Returns the canonical form of the contents of a checksum file: its lines without surrounding whitespace,
+    /// separated by LF. Checksum files rewritten with CRLF line endings, e.g. by git on Windows, compare equal.
+    pub fn normalize(checksum: &str) -> String {
+        checksum.trim().lines().map(str::trim).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Reads the checksum of the program alone, which is the first line of the checksum file.
+    /// The checksum of a program in `src/` is followed by a line with its checksum combined with the input files.
+    pub fn read_program_checksum(&self, path: &Path) -> Result<String> {
+        Ok(self.read_from(path)?.lines().next().unwrap_or_default().to_string())
+    }
+
+    /// Reads the checksums from the given file path, and the version of the compiler that built the program if it
+    /// is recorded.
+    pub fn read_with_compiler_version(&self, path: &Path) -> Result<(String, Option<String>)> {
+        let contents = self.read_from(path)?;
+        let (checksums, version): (Vec<_>, Vec<_>) =
+            contents.lines().partition(|line| !line.starts_with(COMPILER_VERSION_PREFIX));
+        let version = version.first().map(|line| line[COMPILER_VERSION_PREFIX.len()..].to_string());
+        Ok((checksums.join("\n"), version))
+    }
+
+    /// Writes the given checksum to a file, followed by the version of the compiler that built the program.
+    pub fn write_with_compiler_version(&self, path: &Path, checksum: &str, version: &str) -> Result<()> {
+        self.write_to(path, format!("{checksum}\n{COMPILER_VERSION_PREFIX}{version}"))
+    }
+
+    /// Writes the given checksum to a file, in its canonical form.
     pub fn write_to(&self, path: &Path, checksum: String) -> Result<()> {
         let path = self.setup_file_path(path);
-        let mut file = File::create(path).map_err(PackageError::io_error_checksum_file)?;
-
-        file.write_all(checksum.as_bytes()).map_err(PackageError::io_error_checksum_file)?;
+        write_atomically(&path, Self::normalize(&checksum).as_bytes()).map_err(PackageError::io_error_checksum_file)?;
         Ok(())
     }
 
@@ -83,8 +172,64 @@ impl ChecksumFile {
             if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
                 path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
             }
-            path.to_mut().push(format!("{}{CHECKSUM_FILE_EXTENSION}", self.package_name));
+            path.to_mut().push(self.file_name());
         }
         path
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_checksum_ignores_inputs() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let algorithm = ChecksumAlgorithm::Sha256;
+        let program = algorithm.checksum(b"program foo.aleo {}");
+        let input = |contents: &str| vec![("inputs/foo.in".to_string(), contents.as_bytes().to_vec())];
+        let combined = algorithm.combined_checksum(&program, &input("[main]"));
+        assert_ne!(combined, algorithm.combined_checksum(&program, &input("[main]\na: u32 = 1u32;")));
+
+        let checksum_file = ChecksumFile::new("foo");
+        let path = directory.path().join(checksum_file.file_name());
+        checksum_file.write_to(&path, format!("{program}\n{combined}")).unwrap();
+        assert_eq!(checksum_file.read_program_checksum(&path).unwrap(), program);
+    }
+
+    #[test]
+    fn test_checksum_records_compiler_version() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let checksum_file = ChecksumFile::new("foo");
+        let path = directory.path().join(checksum_file.file_name());
+        checksum_file.write_to(&path, "sha256:foo".to_string()).unwrap();
+        assert_eq!(checksum_file.read_with_compiler_version(&path).unwrap(), ("sha256:foo".to_string(), None));
+
+        checksum_file.write_with_compiler_version(&path, "sha256:foo\nsha256:bar", "1.10.0").unwrap();
+        assert_eq!(
+            checksum_file.read_with_compiler_version(&path).unwrap(),
+            ("sha256:foo\nsha256:bar".to_string(), Some("1.10.0".to_string()))
+        );
+        assert_eq!(checksum_file.read_program_checksum(&path).unwrap(), "sha256:foo");
+    }
+
+    #[test]
+    fn test_checksum_ignores_line_endings() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let checksum_file = ChecksumFile::new("foo");
+        let path = directory.path().join(checksum_file.file_name());
+        checksum_file.write_with_compiler_version(&path, "sha256:foo\nsha256:bar", "1.10.0").unwrap();
+        let checksum = checksum_file.read_from(&path).unwrap();
+
+        // The file as rewritten by git with CRLF line endings.
+        fs::write(&path, "sha256:foo\r\nsha256:bar\r\nleo 1.10.0\r\n").unwrap();
+        assert_eq!(checksum_file.read_from(&path).unwrap(), checksum);
+        assert_eq!(
+            checksum_file.read_with_compiler_version(&path).unwrap(),
+            ("sha256:foo\nsha256:bar".to_string(), Some("1.10.0".to_string()))
+        );
+    }
+}
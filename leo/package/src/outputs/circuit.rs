@@ -16,30 +16,173 @@
 
 //! The serialized struct output file.
 
-use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use crate::{
+    gzip,
+    outputs::OUTPUTS_DIRECTORY_NAME,
+    read_to_string_decompressed,
+    write_atomically,
+    GZIP_FILE_EXTENSION,
+};
 use leo_errors::{PackageError, Result};
 
-use serde::Deserialize;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
     borrow::Cow,
-    fs::{
-        File,
-        {self},
-    },
-    io::Write,
+    fs::{self},
     path::Path,
 };
 
 pub static CIRCUIT_FILE_EXTENSION: &str = ".json";
 
+/// The suffix of the name of a partial circuit file, before its extension.
+pub static PARTIAL_CIRCUIT_FILE_SUFFIX: &str = ".partial";
+
+/// The directory in `outputs` holding the content-addressed circuits, named by their SHA-256 hash.
+pub static CIRCUIT_STORE_DIRECTORY_NAME: &str = ".circuits";
+
+/// The synthesized circuits of the functions of a program, as written to the circuit file.
+/// Field elements are written in decimal, so that the file doesn't depend on their binary encoding.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedCircuit {
+    /// The ID of the program, e.g. `foo.aleo`.
+    pub program: String,
+    /// The only function synthesized, if the circuit is partial, as built by `--only-function`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_function: Option<String>,
+    /// The SHA-256 hash of the circuits of the functions, in hex, so that a corrupted or edited file is detected.
+    /// It is computed by `to_json_string` and checked by `from_json_string`, whatever its value in memory.
+    pub checksum: String,
+    /// The circuit of each function of the program, by name.
+    pub functions: IndexMap<String, SerializedFunctionCircuit>,
+}
+
+/// A circuit as it is serialized, with its checksum computed, so that the circuit doesn't need to be copied.
+#[derive(Serialize)]
+struct ChecksummedCircuit<'a> {
+    program: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    only_function: &'a Option<String>,
+    checksum: String,
+    functions: &'a IndexMap<String, SerializedFunctionCircuit>,
+}
+
+/// The circuit of a function, as the constraints `a * b = c` over its public and private variables.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedFunctionCircuit {
+    pub num_public: u64,
+    pub num_private: u64,
+    /// The linear combinations `[a, b, c]` of each constraint, in the order in which they are enforced.
+    pub constraints: Vec<[SerializedLinearCombination; 3]>,
+}
+
+/// A linear combination of the variables of a circuit.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedLinearCombination {
+    pub constant: String,
+    /// The variables of the linear combination, with their coefficients.
+    pub terms: Vec<(SerializedVariable, String)>,
+}
+
+/// A variable of a circuit: a constant, or a public or private variable by index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SerializedVariable {
+    Constant(String),
+    Public(u64),
+    Private(u64),
+}
+
+impl SerializedCircuit {
+    /// Returns the checksum of the circuits of the functions: the SHA-256 hash of their compact JSON, in hex.
+    pub fn compute_checksum(&self) -> Result<String> {
+        let functions = serde_json::to_vec(&self.functions).map_err(PackageError::io_error_circuit_file)?;
+        Ok(format!("{:x}", Sha256::digest(functions)))
+    }
+
+    /// Returns the circuit serialized as JSON, with its checksum.
+    pub fn to_json_string(&self) -> Result<String> {
+        let checksum = self.compute_checksum()?;
+        let circuit = ChecksummedCircuit {
+            program: &self.program,
+            only_function: &self.only_function,
+            checksum,
+            functions: &self.functions,
+        };
+        Ok(serde_json::to_string_pretty(&circuit).map_err(PackageError::io_error_circuit_file)?)
+    }
+
+    /// Returns the circuit deserialized from JSON, failing if its circuits don't match its checksum.
+    pub fn from_json_string(json: &str) -> Result<Self> {
+        let circuit: Self = serde_json::from_str(json).map_err(PackageError::failed_to_parse_circuit_file)?;
+        let checksum = circuit.compute_checksum()?;
+        if circuit.checksum != checksum {
+            return Err(PackageError::circuit_checksum_mismatch(&circuit.program, &circuit.checksum, checksum).into());
+        }
+        Ok(circuit)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct CircuitFile {
     pub package_name: String,
+    /// Whether the file is written compressed with gzip.
+    #[serde(default)]
+    pub compressed: bool,
+    /// Whether the circuit is written to the store of its directory, with a pointer to it in place of the file.
+    #[serde(default)]
+    pub content_addressed: bool,
+    /// The only function of a partial circuit, which is written to a file of its own.
+    #[serde(default)]
+    pub only_function: Option<String>,
+}
+
+/// The file written in place of a content-addressed circuit.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CircuitPointer {
+    /// The SHA-256 hash of the circuit, in hex.
+    content_address: String,
 }
 
 impl CircuitFile {
     pub fn new(package_name: &str) -> Self {
-        Self { package_name: package_name.to_string() }
+        Self {
+            package_name: package_name.to_string(),
+            compressed: false,
+            content_addressed: false,
+            only_function: None,
+        }
+    }
+
+    /// Returns the file of the partial circuit of the given function, if any, written to
+    /// `outputs/{package}.{function}.partial.json` rather than to the circuit file of the package.
+    pub fn with_only_function(self, only_function: Option<String>) -> Self {
+        Self { only_function, ..self }
+    }
+
+    /// Returns the name of the file, e.g. `foo.json`, or `foo.main.partial.json` for a partial circuit.
+    pub fn file_name(&self) -> String {
+        let name = match &self.only_function {
+            Some(function) => format!("{}.{function}{PARTIAL_CIRCUIT_FILE_SUFFIX}", self.package_name),
+            None => self.package_name.clone(),
+        };
+        match self.compressed {
+            true => format!("{name}{CIRCUIT_FILE_EXTENSION}{GZIP_FILE_EXTENSION}"),
+            false => format!("{name}{CIRCUIT_FILE_EXTENSION}"),
+        }
+    }
+
+    /// Returns the file with the given compression, which adds the `.gz` extension to its file name.
+    pub fn with_compression(self, compressed: bool) -> Self {
+        Self { compressed, ..self }
+    }
+
+    /// Returns the file with the given addressing. A content-addressed circuit is written once to
+    /// `outputs/.circuits/<sha256>.json`, so the packages sharing an output directory share identical circuits.
+    pub fn with_content_addressing(self, content_addressed: bool) -> Self {
+        Self { content_addressed, ..self }
     }
 
     pub fn exists_at(&self, path: &Path) -> bool {
@@ -48,25 +191,57 @@ impl CircuitFile {
     }
 
     /// Reads the serialized struct from the given file path if it exists.
+    /// A pointer to a content-addressed circuit is followed, whether or not the file is content-addressed.
     pub fn read_from(&self, path: &Path) -> Result<String> {
         let path = self.setup_file_path(path);
 
-        let string =
-            fs::read_to_string(&path).map_err(|_| PackageError::failed_to_read_circuit_file(path.into_owned()))?;
+        // Compressed files are detected by their contents, so they are read regardless of the extension.
+        let string = read_to_string_decompressed(&path)
+            .map_err(|_| PackageError::failed_to_read_circuit_file(path.to_path_buf()))?;
+
+        let pointer = match serde_json::from_str::<CircuitPointer>(&string) {
+            Ok(pointer) if is_sha256(&pointer.content_address) => pointer,
+            _ => return Ok(string),
+        };
+        let blob_path = path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(CIRCUIT_STORE_DIRECTORY_NAME)
+            .join(format!("{}{CIRCUIT_FILE_EXTENSION}", pointer.content_address));
+        let string = read_to_string_decompressed(&blob_path)
+            .map_err(|_| PackageError::failed_to_read_circuit_file(blob_path))?;
         Ok(string)
     }
 
     /// Writes the given serialized struct to a file.
     pub fn write_to(&self, path: &Path, circuit: String) -> Result<()> {
         let path = self.setup_file_path(path);
-        let mut file = File::create(path).map_err(PackageError::io_error_circuit_file)?;
+        // The circuit is addressed by the hash of its serialization, so its blob is the same whether or not it is compressed.
+        let content_address = format!("{:x}", Sha256::digest(circuit.as_bytes()));
+        let contents = match self.compressed {
+            true => gzip(circuit.as_bytes()).map_err(PackageError::io_error_circuit_file)?,
+            false => circuit.into_bytes(),
+        };
+        if !self.content_addressed {
+            write_atomically(&path, &contents).map_err(PackageError::io_error_circuit_file)?;
+            return Ok(());
+        }
+
+        let store_path = path.parent().unwrap_or(Path::new("")).join(CIRCUIT_STORE_DIRECTORY_NAME);
+        let blob_path = store_path.join(format!("{content_address}{CIRCUIT_FILE_EXTENSION}"));
+        if !blob_path.exists() {
+            fs::create_dir_all(&store_path).map_err(PackageError::io_error_circuit_file)?;
+            write_atomically(&blob_path, &contents).map_err(PackageError::io_error_circuit_file)?;
+        }
 
-        file.write_all(circuit.as_bytes()).map_err(PackageError::io_error_circuit_file)?;
+        let pointer = serde_json::to_string(&CircuitPointer { content_address }).expect("a pointer is serializable");
+        write_atomically(&path, pointer.as_bytes()).map_err(PackageError::io_error_circuit_file)?;
         Ok(())
     }
 
     /// Removes the serialized struct at the given path if it exists. Returns `true` on success,
     /// `false` if the file doesn't exist, and `Error` if the file system fails during operation.
+    /// Only the pointer of a content-addressed circuit is removed, as its blob may be shared with other packages.
     pub fn remove(&self, path: &Path) -> Result<bool> {
         let path = self.setup_file_path(path);
         if !path.exists() {
@@ -83,8 +258,61 @@ impl CircuitFile {
             if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
                 path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
             }
-            path.to_mut().push(format!("{}{CIRCUIT_FILE_EXTENSION}", self.package_name));
+            path.to_mut().push(self.file_name());
         }
         path
     }
 }
+
+/// Returns whether the given string is a SHA-256 hash in hex, so that a pointer can't address a file outside of the store.
+fn is_sha256(string: &str) -> bool {
+    string.len() == 64 && string.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_addressed_circuits_share_a_blob() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let foo = CircuitFile::new("foo").with_content_addressing(true);
+        let bar = CircuitFile::new("bar").with_content_addressing(true).with_compression(true);
+        foo.write_to(directory.path(), "{\"circuit\":1}".to_string()).unwrap();
+        bar.write_to(directory.path(), "{\"circuit\":1}".to_string()).unwrap();
+        let store = directory.path().join(OUTPUTS_DIRECTORY_NAME).join(CIRCUIT_STORE_DIRECTORY_NAME);
+        assert_eq!(fs::read_dir(store).unwrap().count(), 1);
+
+        // Pointers are followed even by a file that isn't content-addressed.
+        assert_eq!(CircuitFile::new("foo").read_from(directory.path()).unwrap(), "{\"circuit\":1}");
+        assert_eq!(bar.read_from(directory.path()).unwrap(), "{\"circuit\":1}");
+    }
+
+    #[test]
+    fn test_circuit_checksum_detects_edits() {
+        let one = || SerializedLinearCombination {
+            constant: "0".to_string(),
+            terms: vec![(SerializedVariable::Constant("1".to_string()), "1".to_string())],
+        };
+        let function =
+            SerializedFunctionCircuit { num_public: 1, num_private: 0, constraints: vec![[one(), one(), one()]] };
+        let circuit = SerializedCircuit {
+            program: "foo.aleo".to_string(),
+            only_function: None,
+            checksum: String::new(),
+            functions: [("main".to_string(), function)].into_iter().collect(),
+        };
+
+        // The checksum is written with the circuit, and checked as it is read.
+        let json = circuit.to_json_string().unwrap();
+        let read = SerializedCircuit::from_json_string(&json).unwrap();
+        assert_eq!(read.checksum, circuit.compute_checksum().unwrap());
+        assert_eq!(read.functions, circuit.functions);
+
+        let edited = json.replace("\"num_public\": 1", "\"num_public\": 2");
+        assert_ne!(edited, json);
+        let error = SerializedCircuit::from_json_string(&edited).unwrap_err().to_string();
+        assert!(error.contains("corrupted"), "{error}");
+    }
+}
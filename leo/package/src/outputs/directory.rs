@@ -14,6 +14,29 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{
+    outputs::{
+        Snapshot,
+        AST_CACHE_FILE_EXTENSION,
+        AST_SNAPSHOT_FILE_EXTENSION,
+        BUILD_MANIFEST_FILE_EXTENSION,
+        CHECKSUM_FILE_EXTENSION,
+        CIRCUIT_FILE_EXTENSION,
+        CIRCUIT_STORE_DIRECTORY_NAME,
+        CONSTRAINT_REPORT_FILE_EXTENSION,
+        DEPGRAPH_FILE_EXTENSION,
+        INPUT_SCHEMA_FILE_EXTENSION,
+        KEYS_CHECKSUM_FILE_EXTENSION,
+        PARTIAL_CIRCUIT_FILE_SUFFIX,
+        PROOF_FILE_EXTENSION,
+        REPORT_FILE_EXTENSION,
+        STATS_FILE_EXTENSION,
+        WITNESS_FILE_EXTENSION,
+    },
+    GZIP_FILE_EXTENSION,
+    TEMPORARY_FILE_EXTENSION,
+};
+
 use leo_errors::{PackageError, Result};
 
 use std::{
@@ -24,6 +47,26 @@ use std::{
 
 pub static OUTPUTS_DIRECTORY_NAME: &str = "outputs/";
 
+/// The extensions of the files generated by the compiler in the outputs directory, after the name of a program.
+pub static GENERATED_FILE_EXTENSIONS: [&str; 12] = [
+    CHECKSUM_FILE_EXTENSION,
+    KEYS_CHECKSUM_FILE_EXTENSION,
+    BUILD_MANIFEST_FILE_EXTENSION,
+    INPUT_SCHEMA_FILE_EXTENSION,
+    DEPGRAPH_FILE_EXTENSION,
+    STATS_FILE_EXTENSION,
+    REPORT_FILE_EXTENSION,
+    CONSTRAINT_REPORT_FILE_EXTENSION,
+    WITNESS_FILE_EXTENSION,
+    PROOF_FILE_EXTENSION,
+    AST_CACHE_FILE_EXTENSION,
+    CIRCUIT_FILE_EXTENSION,
+];
+
+/// The snapshots of the AST written to the outputs directory, which are named after the snapshot only.
+static SNAPSHOTS: [Snapshot; 4] =
+    [Snapshot::Initial, Snapshot::ImportsResolved, Snapshot::TypeInference, Snapshot::Canonicalization];
+
 pub struct OutputsDirectory;
 
 impl OutputsDirectory {
@@ -34,7 +77,29 @@ impl OutputsDirectory {
             path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
         }
 
-        fs::create_dir_all(&path).map_err(PackageError::failed_to_create_inputs_directory)?;
+        Self::create_at(&path)
+    }
+
+    /// Creates a directory at exactly the provided path, without appending the default directory name.
+    ///
+    /// Missing parent directories are created, and an existing directory is left as it is.
+    pub fn create_at(path: &Path) -> Result<PathBuf> {
+        // The trailing slash of the default directory name is dropped, as it hides a file at the path.
+        if path.components().collect::<PathBuf>().exists() && !path.is_dir() {
+            return Err(PackageError::outputs_path_is_not_a_directory(path.display()).into());
+        }
+
+        let created = !path.exists();
+        fs::create_dir_all(path).map_err(|e| PackageError::failed_to_create_outputs_directory(path.display(), e))?;
+
+        // Only the mode of a directory created here is set, as an existing one may not be owned by the user.
+        #[cfg(unix)]
+        if created {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+                .map_err(|e| PackageError::failed_to_create_outputs_directory(path.display(), e))?;
+        }
+
         Ok(path.to_path_buf())
     }
 
@@ -51,4 +116,115 @@ impl OutputsDirectory {
 
         Ok(format!("(in \"{}\")", path.display()))
     }
+
+    /// Removes the files generated by the compiler from the directory at the provided path, keeping the directory.
+    ///
+    /// Only the files named after one of the given names of programs or entry files are removed, e.g. `foo.sum`
+    /// or `foo.main.partial.json`, with the AST snapshots, the temporary files of interrupted writes, and the store
+    /// of content-addressed circuits. Any other file is left as it is.
+    pub fn remove_generated_files(path: &Path, names: &[String]) -> Result<String> {
+        let mut path = Cow::from(path);
+        if path.is_dir() && !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+            path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+        }
+
+        if path.exists() {
+            let entries = fs::read_dir(&path).map_err(|e| PackageError::failed_to_read_directory(path.display(), e))?;
+            for entry in entries {
+                let file_path = entry.map_err(|e| PackageError::failed_to_read_directory(path.display(), e))?.path();
+                let file_name = file_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+
+                if file_path.is_file() && is_generated_file(file_name, names) {
+                    fs::remove_file(&file_path)
+                        .map_err(|e| PackageError::failed_to_remove_file(file_path.display(), e))?;
+                } else if file_path.is_dir() && file_name == CIRCUIT_STORE_DIRECTORY_NAME {
+                    fs::remove_dir_all(&file_path)
+                        .map_err(|e| PackageError::failed_to_remove_directory(file_path.display(), e))?;
+                }
+            }
+        }
+
+        Ok(format!("(in \"{}\")", path.display()))
+    }
+}
+
+/// Returns whether the file of the given name in the outputs directory is generated for one of the given names.
+fn is_generated_file(file_name: &str, names: &[String]) -> bool {
+    // A temporary file is named after the file it replaces, e.g. `.foo.sum.tmp`.
+    let file_name = match file_name.strip_prefix('.').and_then(|name| name.strip_suffix(TEMPORARY_FILE_EXTENSION)) {
+        Some(replaced) => replaced,
+        None => file_name,
+    };
+    let file_name = file_name.strip_suffix(GZIP_FILE_EXTENSION).unwrap_or(file_name);
+
+    if SNAPSHOTS.iter().any(|snapshot| file_name == format!("{snapshot}{AST_SNAPSHOT_FILE_EXTENSION}")) {
+        return true;
+    }
+
+    names.iter().filter_map(|name| file_name.strip_prefix(name.as_str())).any(|extension| {
+        GENERATED_FILE_EXTENSIONS.contains(&extension)
+            || extension
+                .strip_suffix(CIRCUIT_FILE_EXTENSION)
+                .and_then(|extension| extension.strip_suffix(PARTIAL_CIRCUIT_FILE_SUFFIX))
+                .and_then(|function| function.strip_prefix('.'))
+                .map_or(false, |function| !function.is_empty() && !function.contains('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_existing_directory() {
+        let directory = tempfile::tempdir().unwrap();
+
+        // Creating the outputs directory twice keeps the files in it.
+        let outputs = OutputsDirectory::create(directory.path()).unwrap();
+        fs::write(outputs.join("foo.sum"), "sha256:foo").unwrap();
+        assert_eq!(OutputsDirectory::create(directory.path()).unwrap(), outputs);
+        assert!(outputs.join("foo.sum").exists());
+
+        // The mode of an existing directory is kept.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&outputs, fs::Permissions::from_mode(0o700)).unwrap();
+            OutputsDirectory::create(directory.path()).unwrap();
+            assert_eq!(fs::metadata(&outputs).unwrap().permissions().mode() & 0o777, 0o700);
+        }
+
+        // Missing parent directories are created.
+        let nested = directory.path().join("nested/outputs");
+        assert!(OutputsDirectory::create_at(&nested).unwrap().is_dir());
+    }
+
+    #[test]
+    fn test_remove_generated_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let outputs = OutputsDirectory::create(directory.path()).unwrap();
+        let generated =
+            ["foo.sum", "foo.json.gz", "foo.main.partial.json", "bar.ast_cache.json", ".foo.stats.json.tmp"];
+        let kept = ["foo.in", "notes.json", "baz.sum", "archive.gz", "scratch.tmp"];
+        for file_name in generated.iter().chain(&kept) {
+            fs::write(outputs.join(file_name), "").unwrap();
+        }
+        fs::create_dir(outputs.join(CIRCUIT_STORE_DIRECTORY_NAME)).unwrap();
+        fs::write(outputs.join(CIRCUIT_STORE_DIRECTORY_NAME).join("0123.json"), "").unwrap();
+
+        // Only the files of the given programs are removed, with the store of their circuits.
+        OutputsDirectory::remove_generated_files(directory.path(), &["foo".to_string(), "bar".to_string()]).unwrap();
+        assert!(generated.iter().all(|file_name| !outputs.join(file_name).exists()));
+        assert!(kept.iter().all(|file_name| outputs.join(file_name).exists()));
+        assert!(!outputs.join(CIRCUIT_STORE_DIRECTORY_NAME).exists());
+    }
+
+    #[test]
+    fn test_create_with_file_in_the_way() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join(OUTPUTS_DIRECTORY_NAME.trim_end_matches('/')), "").unwrap();
+
+        let error = OutputsDirectory::create(directory.path()).unwrap_err();
+        assert!(error.to_string().contains("not a directory"));
+    }
 }
@@ -14,16 +14,46 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod ast_cache;
+pub use self::ast_cache::*;
+
 pub mod ast_snapshot;
 pub use self::ast_snapshot::*;
 
+pub mod build_manifest;
+pub use self::build_manifest::*;
+
 pub mod circuit;
 pub use self::circuit::*;
 
 pub mod checksum;
 pub use self::checksum::*;
 
+pub mod constraint_report;
+pub use self::constraint_report::*;
+
+pub mod depgraph;
+pub use self::depgraph::*;
+
 pub mod directory;
 pub use directory::*;
 
+pub mod input_schema;
+pub use self::input_schema::*;
+
+pub mod output_file;
+pub use self::output_file::*;
+
+pub mod proof;
+pub use self::proof::*;
+
+pub mod report;
+pub use self::report::*;
+
+pub mod stats;
+pub use self::stats::*;
+
+pub mod witness;
+pub use self::witness::*;
+
 pub static MAIN_ALEO_FILE_NAME: &str = "main.aleo";
@@ -0,0 +1,71 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A file of the outputs directory named after its package, e.g. `foo.stats.json`.
+
+use crate::{outputs::OUTPUTS_DIRECTORY_NAME, write_atomically};
+use leo_errors::{PackageError, Result};
+
+use std::{borrow::Cow, fs, io, marker::PhantomData, path::Path};
+
+/// A kind of output file, e.g. the program statistics.
+pub trait OutputFileKind {
+    /// The extension of the file, e.g. `.stats.json`.
+    const EXTENSION: &'static str;
+
+    /// Returns the error of a failed write of the file.
+    fn write_error(error: io::Error) -> PackageError;
+}
+
+pub struct OutputFile<K: OutputFileKind> {
+    pub package_name: String,
+    kind: PhantomData<K>,
+}
+
+impl<K: OutputFileKind> OutputFile<K> {
+    pub fn new(package_name: &str) -> Self {
+        Self { package_name: package_name.to_string(), kind: PhantomData }
+    }
+
+    /// Returns the file name of the output file, e.g. `foo.stats.json`.
+    pub fn file_name(&self) -> String {
+        format!("{}{}", self.package_name, K::EXTENSION)
+    }
+
+    /// Reads the output file from the given file path.
+    pub fn read_from(&self, path: &Path) -> Result<String> {
+        let path = self.setup_file_path(path);
+        Ok(fs::read_to_string(&path).map_err(|error| PackageError::failed_to_read_file(path.display(), error))?)
+    }
+
+    /// Writes the given contents to the output file.
+    pub fn write_to(&self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        let path = self.setup_file_path(path);
+        write_atomically(&path, contents.as_ref()).map_err(K::write_error)?;
+        Ok(())
+    }
+
+    fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut().push(self.file_name());
+        }
+        path
+    }
+}
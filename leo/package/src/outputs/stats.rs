@@ -0,0 +1,37 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The program statistics file.
+
+use crate::outputs::{OutputFile, OutputFileKind};
+use leo_errors::PackageError;
+
+use std::io;
+
+pub static STATS_FILE_EXTENSION: &str = StatsFileKind::EXTENSION;
+
+/// The statistics of a compiled program, e.g. `foo.stats.json`.
+pub struct StatsFileKind;
+
+impl OutputFileKind for StatsFileKind {
+    const EXTENSION: &'static str = ".stats.json";
+
+    fn write_error(error: io::Error) -> PackageError {
+        PackageError::io_error_stats_file(error)
+    }
+}
+
+pub type StatsFile = OutputFile<StatsFileKind>;
@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The witness file.
+
+use crate::outputs::{OutputFile, OutputFileKind};
+use leo_errors::{PackageError, Result};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+pub static WITNESS_FILE_EXTENSION: &str = WitnessFileKind::EXTENSION;
+
+/// The values of the variables of the circuits of a program, synthesized on the inputs of its input file.
+/// Field elements are written in decimal, as in the circuit file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedWitness {
+    /// The ID of the program, e.g. `foo.aleo`.
+    pub program: String,
+    /// The witness of each function with a section in the input file, by name.
+    pub functions: IndexMap<String, SerializedFunctionWitness>,
+}
+
+/// The witness of the circuit of a function, indexed as the variables of its serialized circuit.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedFunctionWitness {
+    /// The inputs of the function by register, e.g. `r0`, which are the only variables with a known name.
+    pub registers: IndexMap<String, WitnessRegister>,
+    /// The values of the public variables, by index. The first one is the constant one.
+    pub input_assignment: Vec<String>,
+    /// The values of the private variables, by index.
+    pub aux_assignment: Vec<String>,
+}
+
+/// An input of a function, with its name in the input file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessRegister {
+    pub name: String,
+    pub value: String,
+}
+
+impl SerializedWitness {
+    /// Returns the witness serialized as JSON.
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(PackageError::io_error_witness_file)?)
+    }
+}
+
+/// The witness of a program, e.g. `foo.witness.json`.
+pub struct WitnessFileKind;
+
+impl OutputFileKind for WitnessFileKind {
+    const EXTENSION: &'static str = ".witness.json";
+
+    fn write_error(error: io::Error) -> PackageError {
+        PackageError::io_error_witness_file(error)
+    }
+}
+
+pub type WitnessFile = OutputFile<WitnessFileKind>;
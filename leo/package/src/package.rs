@@ -18,13 +18,58 @@ use crate::{
     build::BuildDirectory,
     inputs::{InputFile, InputsDirectory},
     root::{Env, Gitignore},
-    source::{MainFile, SourceDirectory},
+    source::{LibFile, MainFile, SourceDirectory},
 };
 use leo_errors::{PackageError, Result};
 use snarkvm::console::prelude::Network;
 
 use serde::Deserialize;
-use std::{marker::PhantomData, path::Path};
+use std::{fmt, marker::PhantomData, path::Path, str::FromStr};
+
+/// The starter template a new package is created from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageTemplate {
+    /// A program adding two numbers.
+    #[default]
+    Hello,
+    /// A program minting and transferring token records.
+    Token,
+    /// A program with a transition testing a helper function.
+    Test,
+    /// A library of reusable definitions, in `lib.leo` and without a main file.
+    Lib,
+}
+
+impl PackageTemplate {
+    /// The names of all templates.
+    pub const NAMES: [&'static str; 4] = ["hello", "token", "test", "lib"];
+}
+
+impl fmt::Display for PackageTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Hello => "hello",
+            Self::Token => "token",
+            Self::Test => "test",
+            Self::Lib => "lib",
+        })
+    }
+}
+
+impl FromStr for PackageTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "hello" => Ok(Self::Hello),
+            "token" => Ok(Self::Token),
+            "test" => Ok(Self::Test),
+            "lib" => Ok(Self::Lib),
+            _ => Err(format!("unknown template '{s}', the available templates are: {}", Self::NAMES.join(", "))),
+        }
+    }
+}
 
 #[derive(Deserialize)]
 pub struct Package<N: Network> {
@@ -110,6 +155,12 @@ impl<N: Network> Package<N> {
             result = false;
         }
 
+        // Check if the lib file already exists.
+        if LibFile::exists_at(path) {
+            existing_files.push(LibFile::filename());
+            result = false;
+        }
+
         if !existing_files.is_empty() {
             tracing::error!("File(s) {:?} already exist", existing_files);
         }
@@ -124,6 +175,11 @@ impl<N: Network> Package<N> {
             return false;
         }
 
+        // A library is initialized with only the lib file.
+        if LibFile::exists_at(path) {
+            return true;
+        }
+
         // Check if the input file exists.
         let input_file = InputFile::new(package_name);
         if !input_file.exists_at(path) {
@@ -140,6 +196,11 @@ impl<N: Network> Package<N> {
 
     /// Creates a Leo package at the given path
     pub fn initialize(package_name: &str, path: &Path) -> Result<()> {
+        Self::initialize_with_template(package_name, path, PackageTemplate::default())
+    }
+
+    /// Creates a Leo package at the given path from the given starter template.
+    pub fn initialize_with_template(package_name: &str, path: &Path, template: PackageTemplate) -> Result<()> {
         // Verify that the .gitignore file does not exist.
         if !Gitignore::exists_at(path) {
             // Create the .gitignore file.
@@ -161,11 +222,17 @@ impl<N: Network> Package<N> {
         // Create the Leo build/ directory
         BuildDirectory::create(path)?;
 
-        // Create the input file in the inputs directory.
-        InputFile::new(package_name).write_to(path)?;
+        match template {
+            // Create the lib file in the source directory.
+            PackageTemplate::Lib => LibFile::new(package_name).write_to(path)?,
+            _ => {
+                // Create the input file in the inputs directory.
+                InputFile::new(package_name).with_template(template).write_to(path)?;
 
-        // Create the main file in the source directory.
-        MainFile::new(package_name).write_to(path)?;
+                // Create the main file in the source directory.
+                MainFile::new(package_name).with_template(template).write_to(path)?;
+            }
+        }
 
         // Next, verify that a valid Leo package has been initialized in this directory
         if !Self::is_initialized(package_name, path) {
@@ -210,4 +277,14 @@ mod tests {
         assert!(!Package::<CurrentNetwork>::is_package_name_valid("foo,bar"));
         assert!(!Package::<CurrentNetwork>::is_package_name_valid("1-foo"));
     }
+
+    #[test]
+    fn test_package_template_from_str() {
+        for name in PackageTemplate::NAMES {
+            assert_eq!(name.parse::<PackageTemplate>().unwrap().to_string(), name);
+        }
+
+        let error = "example".parse::<PackageTemplate>().unwrap_err();
+        assert!(error.contains("hello, token, test, lib"));
+    }
 }
@@ -0,0 +1,144 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `.leoignore` file.
+
+use leo_errors::{PackageError, Result};
+
+use std::{fs, path::Path};
+
+pub static LEOIGNORE_FILENAME: &str = ".leoignore";
+
+/// The patterns of a `.leoignore` file, which exclude source and import files from compilation.
+///
+/// The patterns follow a subset of the `.gitignore` syntax: `*` and `?` match within a file name, `**` matches
+/// across directories, a trailing `/` only matches directories, a pattern containing a `/` is relative to the
+/// package root, and a leading `!` includes the files excluded by the patterns before it.
+#[derive(Clone, Debug, Default)]
+pub struct Leoignore {
+    patterns: Vec<IgnorePattern>,
+}
+
+/// A single line of a `.leoignore` file.
+#[derive(Clone, Debug)]
+struct IgnorePattern {
+    glob: String,
+    negated: bool,
+    anchored: bool,
+    directory: bool,
+}
+
+impl Leoignore {
+    /// Reads the `.leoignore` file in the package root, which ignores nothing if it doesn't exist.
+    pub fn read_from(package_path: &Path) -> Result<Self> {
+        let path = package_path.join(LEOIGNORE_FILENAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let string = fs::read_to_string(&path).map_err(PackageError::io_error_leoignore_file)?;
+        Ok(Self::parse(&string))
+    }
+
+    /// Parses the patterns of a `.leoignore` file, skipping blank lines and `#` comments.
+    pub fn parse(string: &str) -> Self {
+        let patterns = string
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(line) => (true, line),
+                    None => (false, line),
+                };
+                let (directory, line) = match line.strip_suffix('/') {
+                    Some(line) => (true, line),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                IgnorePattern { glob: line.trim_start_matches('/').to_string(), negated, anchored, directory }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Returns `true` if the file at the given path in the package is ignored.
+    /// As in `.gitignore`, a file is ignored if it or one of its directories matches the last pattern it matches.
+    pub fn is_ignored(&self, package_path: &Path, path: &Path) -> bool {
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let Ok(relative) = canonical(path).strip_prefix(canonical(package_path)).map(Path::to_path_buf) else {
+            return false;
+        };
+        let components =
+            relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>();
+
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            let matches = (1..=components.len()).any(|length| {
+                // Only the directories of the file are matched by patterns ending with a `/`.
+                if pattern.directory && length == components.len() {
+                    return false;
+                }
+                match pattern.anchored {
+                    true => glob_matches(pattern.glob.as_bytes(), components[..length].join("/").as_bytes()),
+                    false => glob_matches(pattern.glob.as_bytes(), components[length - 1].as_bytes()),
+                }
+            });
+            if matches {
+                ignored = !pattern.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Returns `true` if the given text matches the glob, whose `*` and `?` don't match a `/`.
+fn glob_matches(glob: &[u8], text: &[u8]) -> bool {
+    match glob {
+        [] => text.is_empty(),
+        // A `**/` matches any number of directories, including none.
+        [b'*', b'*', b'/', rest @ ..] => {
+            (0..=text.len()).any(|i| (i == 0 || text[i - 1] == b'/') && glob_matches(rest, &text[i..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_matches(rest, &text[i..])),
+        [b'*', rest @ ..] => {
+            (0..=text.len()).take_while(|&i| i == 0 || text[i - 1] != b'/').any(|i| glob_matches(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => matches!(text, [c, text @ ..] if *c != b'/' && glob_matches(rest, text)),
+        [c, rest @ ..] => matches!(text, [t, text @ ..] if t == c && glob_matches(rest, text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leoignore_matches_gitignore_style_patterns() {
+        let package = Path::new("/foo");
+        let leoignore = Leoignore::parse("# Work in progress.\nwip*.leo\n/src/drafts/\n**/old/**\n!src/wip_keep.leo\n");
+        let is_ignored = |path: &str| leoignore.is_ignored(package, &package.join(path));
+
+        assert!(is_ignored("src/wip.leo"));
+        assert!(is_ignored("src/nested/wip_more.leo"));
+        assert!(!is_ignored("src/wip_keep.leo"));
+        assert!(is_ignored("src/drafts/a.leo"));
+        assert!(!is_ignored("src/drafts.leo"));
+        assert!(is_ignored("imports/old/a.leo"));
+        assert!(!is_ignored("src/main.leo"));
+        assert!(!leoignore.is_ignored(package, Path::new("/bar/src/wip.leo")));
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The Leo-specific configuration of the `program.json` manifest.
+
+use crate::outputs::ChecksumAlgorithm;
+use leo_errors::{PackageError, Result};
+
+use serde::Deserialize;
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub static MANIFEST_FILENAME: &str = "program.json";
+
+/// The Leo-specific sections of the `program.json` manifest.
+/// These sections are ignored by snarkVM, and missing sections take their default values.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ManifestConfig {
+    /// The program ID, which is read here to be validated before snarkVM parses it.
+    pub program: String,
+    /// The `build` section.
+    pub build: BuildConfig,
+    /// The `workspace` section, which is only set in the manifest at the root of a workspace.
+    pub workspace: WorkspaceConfig,
+    /// The `imports` section.
+    pub imports: ImportsConfig,
+    /// The `compiler` section.
+    pub compiler: CompilerConfig,
+    /// The `bins` section, listing the entry files built by `leo build` instead of `main.leo`.
+    pub bins: Vec<BinConfig>,
+    /// The `hooks` section.
+    pub hooks: HooksConfig,
+}
+
+/// The `hooks` section of the manifest, holding shell commands run by `leo build`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// The command run after a build which compiled a program, as `--post-build` sets it.
+    pub post_build: Option<String>,
+}
+
+/// An entry of the `bins` section of the manifest, naming an entry file of the package.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BinConfig {
+    /// The name of the entry, which `--bin` selects it by.
+    pub name: String,
+    /// The path of the entry file, relative to the package.
+    pub path: PathBuf,
+}
+
+/// The `build` section of the manifest.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct BuildConfig {
+    /// The algorithm used to compute the checksums of compiled programs.
+    pub checksum_algorithm: ChecksumAlgorithm,
+    /// The directory holding the Leo files of the program relative to the package, replacing `src/`.
+    pub source_dir: Option<PathBuf>,
+}
+
+/// The `workspace` section of the manifest.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// The paths of the member packages, relative to the workspace root.
+    pub members: Vec<String>,
+}
+
+/// The `imports` section of the manifest, mapping imports to Leo files outside of the `imports/` directory.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportsConfig {
+    /// The directories the mapped Leo files must be in, relative to the package. Defaults to the package itself.
+    pub roots: Vec<PathBuf>,
+    /// The paths of the Leo files, relative to the package, by import name.
+    pub paths: BTreeMap<String, PathBuf>,
+}
+
+/// The `compiler` section of the manifest, holding the defaults of compiler options, which flags override.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CompilerConfig {
+    /// Enables dead code elimination, as `--enable-dce` does.
+    pub optimize: bool,
+    /// Fails the build if the compiler emits any warnings, as `--deny-warnings` does.
+    pub deny_warnings: bool,
+    /// The curve to compile the program for, as `--curve` sets it.
+    pub curve: Option<String>,
+    /// The most constraints a function may have, as `--max-constraints` sets it.
+    pub max_constraints: Option<u64>,
+    /// The size in bytes of a compiled program above which the build warns, as `--warn-program-size` sets it.
+    pub warn_program_size: Option<u64>,
+    /// The keys not known to this version of Leo, which are ignored so that newer manifests can be read.
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>,
+}
+
+impl ManifestConfig {
+    /// Reads the Leo-specific configuration from the manifest at the given path.
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            path.to_mut().push(MANIFEST_FILENAME);
+        }
+
+        let string = fs::read_to_string(&path).map_err(PackageError::failed_to_open_manifest)?;
+        let config = serde_json::from_str(&string).map_err(|error| parse_error(&path, &string, error))?;
+        Ok(config)
+    }
+
+    /// Returns the configuration of a package without a manifest, whose program is named after its directory.
+    pub fn synthetic(path: &Path) -> Result<Self> {
+        let path = path.canonicalize().map_err(PackageError::failed_to_open_manifest)?;
+        let name = path.file_name().and_then(|name| name.to_str()).ok_or_else(PackageError::failed_to_get_file_name)?;
+        Ok(Self { program: format!("{name}.aleo"), ..Default::default() })
+    }
+
+    /// Returns `true` if the manifest is at the root of a workspace.
+    pub fn is_workspace(&self) -> bool {
+        !self.workspace.members.is_empty()
+    }
+
+    /// Returns an error if the program name in the manifest is not a valid identifier.
+    /// Program names follow the rules of `Package::is_package_name_valid`.
+    pub fn validate(&self) -> Result<()> {
+        let name = self.program.strip_suffix(".aleo").unwrap_or(&self.program);
+
+        // List each offending character once, in the order they appear.
+        let mut characters = Vec::new();
+        for character in name.chars().filter(|c| !c.is_ascii_alphanumeric() && *c != '_') {
+            if !characters.contains(&character) {
+                characters.push(character);
+            }
+        }
+
+        let reason = match name.chars().next() {
+            None => "it is empty".to_string(),
+            Some(_) if !characters.is_empty() => format!(
+                "it contains the characters {}",
+                characters.iter().map(|c| format!("`{c}`")).collect::<Vec<_>>().join(", ")
+            ),
+            Some('_') => "it begins with an underscore".to_string(),
+            Some(first) if first.is_numeric() => "it begins with a number".to_string(),
+            Some(_) => return Ok(()),
+        };
+
+        Err(PackageError::invalid_program_name(&self.program, reason).into())
+    }
+}
+
+/// Returns the error of a manifest which isn't valid JSON, or doesn't match the expected sections, pointing to the
+/// line and column of the error in its source.
+fn parse_error(path: &Path, source: &str, error: serde_json::Error) -> PackageError {
+    // The position is appended to the message, so it is removed to be printed with the snippet instead.
+    let message = error.to_string();
+    let message = message.rsplit_once(" at line ").map_or(message.as_str(), |(message, _)| message);
+    // An error at a line break, e.g. in a string missing its closing quote, is at column 0 of the next line.
+    let (line, column) = match (error.line(), error.column()) {
+        (line, 0) if line > 1 => (line - 1, source.lines().nth(line - 2).map_or(0, |text| text.chars().count()) + 1),
+        position => position,
+    };
+    PackageError::failed_to_parse_manifest(path.display(), line, column, message, error_snippet(source, line, column))
+}
+
+/// Returns the given line of the source, with a caret under the given column. Lines and columns start at 1.
+fn error_snippet(source: &str, line: usize, column: usize) -> String {
+    let Some(text) = line.checked_sub(1).and_then(|index| source.lines().nth(index)) else {
+        return String::new();
+    };
+    let number = line.to_string();
+    let padding = " ".repeat(number.len());
+    let caret = format!("{}^", " ".repeat(column.saturating_sub(1)));
+    format!("{padding} |\n{number} | {text}\n{padding} | {caret}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn test_manifest_compiler_section_keeps_unknown_keys() {
+        let directory = tempfile::tempdir().unwrap();
+        let manifest = r#"{"program": "foo.aleo", "compiler": {"optimize": true, "curve": "bls12_377", "max_constraints": 1000, "future": 1}}"#;
+        fs::write(directory.path().join(MANIFEST_FILENAME), manifest).unwrap();
+
+        let config = ManifestConfig::read_from(directory.path()).unwrap();
+        assert!(config.compiler.optimize);
+        assert!(!config.compiler.deny_warnings);
+        assert_eq!(config.compiler.curve.as_deref(), Some("bls12_377"));
+        assert_eq!(config.compiler.max_constraints, Some(1000));
+        assert_eq!(config.compiler.unknown.keys().collect::<Vec<_>>(), vec!["future"]);
+    }
+
+    #[test]
+    fn test_manifest_parse_errors_point_to_the_error() {
+        let directory = tempfile::tempdir().unwrap();
+
+        for (manifest, line, column) in [
+            // A string missing its closing quote.
+            ("{\n  \"program\": \"foo.aleo,\n  \"version\": \"0.0.0\"\n}\n", 2, 24),
+            // A section which isn't an object.
+            ("{\n  \"program\": \"foo.aleo\",\n  \"compiler\": [\"optimize\"]\n}\n", 3, 14),
+            // A trailing comma.
+            ("{\n  \"program\": \"foo.aleo\",\n}\n", 3, 1),
+        ] {
+            fs::write(directory.path().join(MANIFEST_FILENAME), manifest).unwrap();
+            let error = ManifestConfig::read_from(directory.path()).unwrap_err().to_string();
+            assert!(error.contains(&format!("at line {line}, column {column}")), "{error}");
+            let text = manifest.lines().nth(line - 1).unwrap();
+            assert!(error.contains(&format!("{line} | {text}")), "{error}");
+        }
+    }
+}
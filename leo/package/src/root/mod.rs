@@ -18,3 +18,9 @@ pub use self::env::*;
 
 pub mod gitignore;
 pub use self::gitignore::*;
+
+pub mod leoignore;
+pub use self::leoignore::*;
+
+pub mod manifest;
+pub use self::manifest::*;
@@ -18,7 +18,7 @@ use crate::parse_file_paths;
 
 use leo_errors::{PackageError, Result};
 
-use crate::source::MAIN_FILENAME;
+use crate::source::{LIB_FILENAME, MAIN_FILENAME};
 use std::{
     borrow::Cow,
     fs,
@@ -47,8 +47,12 @@ impl SourceDirectory {
         if path.is_dir() && !path.ends_with(SOURCE_DIRECTORY_NAME) {
             path.to_mut().push(SOURCE_DIRECTORY_NAME);
         }
+        Self::files_in(&path)
+    }
 
-        let directory = fs::read_dir(&path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
+    /// Returns a list of files in the given source directory, which may have another name than `src/`.
+    pub fn files_in(path: &Path) -> Result<Vec<PathBuf>> {
+        let directory = fs::read_dir(path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
         let mut file_paths = Vec::new();
 
         parse_file_paths(directory, &mut file_paths)?;
@@ -60,7 +64,7 @@ impl SourceDirectory {
     pub fn check_files(paths: &[PathBuf]) -> Result<()> {
         match paths.len() {
             0 => Err(PackageError::empty_source_directory().into()),
-            1 if paths[0].as_path().ends_with(MAIN_FILENAME) => Ok(()),
+            1 if paths[0].as_path().ends_with(MAIN_FILENAME) || paths[0].as_path().ends_with(LIB_FILENAME) => Ok(()),
             _ => Err(PackageError::source_directory_can_contain_only_one_file().into()),
         }
     }
@@ -16,7 +16,7 @@
 
 //! The `main.leo` file.
 
-use crate::source::directory::SOURCE_DIRECTORY_NAME;
+use crate::{package::PackageTemplate, source::directory::SOURCE_DIRECTORY_NAME};
 use leo_errors::{PackageError, Result};
 
 use serde::Deserialize;
@@ -27,11 +27,19 @@ pub static MAIN_FILENAME: &str = "main.leo";
 #[derive(Deserialize)]
 pub struct MainFile {
     pub package_name: String,
+    #[serde(default)]
+    pub template: PackageTemplate,
 }
 
 impl MainFile {
     pub fn new(package_name: &str) -> Self {
-        Self { package_name: package_name.to_string() }
+        Self { package_name: package_name.to_string(), template: PackageTemplate::default() }
+    }
+
+    /// Returns the main file written from the given starter template.
+    pub fn with_template(mut self, template: PackageTemplate) -> Self {
+        self.template = template;
+        self
     }
 
     pub fn filename() -> String {
@@ -64,16 +72,59 @@ impl MainFile {
 
     // TODO: Generalize to other networks.
     fn template(&self) -> String {
-        format!(
-            r#"// The '{}' program.
-program {}.aleo {{
+        let name = &self.package_name;
+        match self.template {
+            PackageTemplate::Token => format!(
+                r#"// The '{name}' program.
+program {name}.aleo {{
+    record token {{
+        owner: address,
+        amount: u64,
+    }}
+
+    transition mint(receiver: address, amount: u64) -> token {{
+        return token {{ owner: receiver, amount: amount }};
+    }}
+
+    transition transfer(sender: token, receiver: address, amount: u64) -> (token, token) {{
+        let difference: u64 = sender.amount - amount;
+        let remaining: token = token {{ owner: sender.owner, amount: difference }};
+        let transferred: token = token {{ owner: receiver, amount: amount }};
+        return (remaining, transferred);
+    }}
+}}
+"#
+            ),
+            PackageTemplate::Test => format!(
+                r#"// The '{name}' program.
+program {name}.aleo {{
+    inline add(a: u32, b: u32) -> u32 {{
+        return a + b;
+    }}
+
+    transition main(public a: u32, b: u32) -> u32 {{
+        return add(a, b);
+    }}
+
+    // Run with `leo test` to check `add` against the expected sum.
+    @test
+    transition test_add(a: u32, b: u32, expected: u32) {{
+        assert_eq(add(a, b), expected);
+    }}
+}}
+"#
+            ),
+            // The lib template is written by `LibFile`.
+            PackageTemplate::Hello | PackageTemplate::Lib => format!(
+                r#"// The '{name}' program.
+program {name}.aleo {{
     transition main(public a: u32, b: u32) -> u32 {{
         let c: u32 = a + b;
         return c;
     }}
 }}
-"#,
-            self.package_name, self.package_name
-        )
+"#
+            ),
+        }
     }
 }
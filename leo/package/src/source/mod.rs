@@ -17,5 +17,8 @@
 pub mod directory;
 pub use directory::*;
 
+pub mod lib;
+pub use lib::*;
+
 pub mod main;
 pub use main::*;